@@ -0,0 +1,314 @@
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Select};
+use similar::{ChangeTag, TextDiff};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use devgeini::DevgeiniError;
+
+use crate::cli::wizard::prompt_error;
+
+/// What to do when the project directory already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverwriteMode {
+    /// Resolve every conflicting file interactively, showing a diff
+    Ask,
+    /// Move the existing directory to a timestamped backup and start fresh
+    Force,
+    /// Only write files that do not exist yet
+    Merge,
+}
+
+#[derive(Default)]
+pub struct MergeSummary {
+    pub created: usize,
+    pub overwritten: usize,
+    pub kept: usize,
+    pub unchanged: usize,
+}
+
+/// Refuses to touch a directory tracked by git that has uncommitted changes.
+pub fn ensure_no_uncommitted_changes(path: &Path) -> Result<(), DevgeiniError> {
+    let output = match Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(["status", "--porcelain", "--", "."])
+        .output()
+    {
+        Ok(output) => output,
+        // git is not installed, so there is nothing we could lose
+        Err(_) => return Ok(()),
+    };
+
+    // A non-zero exit means the directory is not inside a git work tree
+    if output.status.success() && !output.stdout.is_empty() {
+        return Err(DevgeiniError::Conflict(format!(
+            "'{}' is a git repository with uncommitted changes. Commit or stash them first.",
+            path.display()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Renames `path` to `<path>.backup-<timestamp>` and returns the new location.
+pub fn backup_existing(path: &Path) -> Result<PathBuf, DevgeiniError> {
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| DevgeiniError::Conflict(format!("cannot back up '{}'", path.display())))?
+        .to_string_lossy();
+    let backup_path = path.with_file_name(format!("{}.backup-{}", file_name, timestamp));
+
+    fs::rename(path, &backup_path).map_err(|e| DevgeiniError::io(path, e))?;
+    Ok(backup_path)
+}
+
+/// Copies every file generated under `generated` into `target` according to `mode`.
+///
+/// Conflicts are found before anything is copied, so a conflict that cannot be asked about
+/// without a terminal leaves `target` untouched.
+pub fn apply_generated(generated: &Path, target: &Path, mode: OverwriteMode) -> Result<MergeSummary, DevgeiniError> {
+    let mut summary = MergeSummary::default();
+    let mut pending = Vec::new();
+
+    for relative in collect_files(generated)? {
+        let source = generated.join(&relative);
+        let destination = target.join(&relative);
+        let new_contents = fs::read(&source).map_err(|e| DevgeiniError::io(&source, e))?;
+
+        let old_contents = if destination.exists() {
+            let old_contents = fs::read(&destination).map_err(|e| DevgeiniError::io(&destination, e))?;
+            if old_contents == new_contents {
+                summary.unchanged += 1;
+                continue;
+            }
+            Some(old_contents)
+        } else {
+            None
+        };
+        pending.push((relative, new_contents, old_contents));
+    }
+
+    let conflicts = pending.iter().filter(|(_, _, old_contents)| old_contents.is_some()).count();
+    if mode == OverwriteMode::Ask && conflicts > 0 && !std::io::stdin().is_terminal() {
+        return Err(DevgeiniError::Conflict(format!(
+            "{} generated file(s) already exist in '{}' with other contents and there is no terminal to ask about them; pass --force to back up and replace the directory or --merge to keep the existing files",
+            conflicts,
+            target.display()
+        )));
+    }
+
+    let mut sticky_choice: Option<bool> = None;
+    for (relative, new_contents, old_contents) in pending {
+        let destination = target.join(&relative);
+
+        let Some(old_contents) = old_contents else {
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent).map_err(|e| DevgeiniError::io(parent, e))?;
+            }
+            fs::write(&destination, &new_contents).map_err(|e| DevgeiniError::io(&destination, e))?;
+            summary.created += 1;
+            continue;
+        };
+
+        let overwrite = match mode {
+            OverwriteMode::Merge => false,
+            OverwriteMode::Force => true,
+            OverwriteMode::Ask => match sticky_choice {
+                Some(choice) => choice,
+                None => {
+                    print_diff(&relative, &old_contents, &new_contents);
+                    let (choice, sticky) = ask_conflict(&relative)?;
+                    if sticky {
+                        sticky_choice = Some(choice);
+                    }
+                    choice
+                }
+            },
+        };
+
+        if overwrite {
+            fs::write(&destination, &new_contents).map_err(|e| DevgeiniError::io(&destination, e))?;
+            summary.overwritten += 1;
+        } else {
+            summary.kept += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+// Relative paths of all files below `root`, sorted so prompts come in a stable order
+fn collect_files(root: &Path) -> Result<Vec<PathBuf>, DevgeiniError> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir).map_err(|e| DevgeiniError::io(&dir, e))?;
+        for entry in entries {
+            let path = entry.map_err(|e| DevgeiniError::io(&dir, e))?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(root) {
+                files.push(relative.to_path_buf());
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn print_diff(relative: &Path, old_contents: &[u8], new_contents: &[u8]) {
+    println!("\n⚠️  {} already exists and differs:", relative.display().to_string().bold());
+
+    let (Ok(old_text), Ok(new_text)) = (std::str::from_utf8(old_contents), std::str::from_utf8(new_contents)) else {
+        println!("   (binary file differs)");
+        return;
+    };
+
+    let diff = TextDiff::from_lines(old_text, new_text);
+    for change in diff.iter_all_changes() {
+        let line = change.to_string_lossy();
+        let line = line.trim_end_matches('\n');
+        match change.tag() {
+            ChangeTag::Delete => println!("{}", format!("-{}", line).red()),
+            ChangeTag::Insert => println!("{}", format!("+{}", line).green()),
+            ChangeTag::Equal => {}
+        }
+    }
+}
+
+// Returns (overwrite?, apply to all remaining conflicts?)
+fn ask_conflict(relative: &Path) -> Result<(bool, bool), DevgeiniError> {
+    let options = vec![
+        "Keep existing file",
+        "Overwrite with generated file",
+        "Keep all remaining existing files",
+        "Overwrite all remaining files",
+    ];
+
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("What should happen to {}?", relative.display()))
+        .items(&options)
+        .default(0)
+        .interact()
+        .map_err(prompt_error)?;
+
+    Ok(match selection {
+        0 => (false, false),
+        1 => (true, false),
+        2 => (false, true),
+        _ => (true, true),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A generated tree and an existing project that share `README.md` with other contents
+    fn generated_and_existing() -> (tempfile::TempDir, tempfile::TempDir) {
+        let generated = tempfile::tempdir().unwrap();
+        fs::create_dir_all(generated.path().join("src")).unwrap();
+        fs::write(generated.path().join("README.md"), "# generated\n").unwrap();
+        fs::write(generated.path().join("src").join("main.rs"), "fn main() {}\n").unwrap();
+        fs::write(generated.path().join(".gitignore"), "target/\n").unwrap();
+
+        let existing = tempfile::tempdir().unwrap();
+        fs::write(existing.path().join("README.md"), "# mine\n").unwrap();
+        fs::write(existing.path().join(".gitignore"), "target/\n").unwrap();
+        (generated, existing)
+    }
+
+    #[test]
+    fn merge_keeps_existing_files_and_adds_new_ones() {
+        let (generated, existing) = generated_and_existing();
+
+        let summary = apply_generated(generated.path(), existing.path(), OverwriteMode::Merge).unwrap();
+
+        assert_eq!(fs::read_to_string(existing.path().join("README.md")).unwrap(), "# mine\n");
+        assert_eq!(fs::read_to_string(existing.path().join("src").join("main.rs")).unwrap(), "fn main() {}\n");
+        assert_eq!(
+            (summary.created, summary.overwritten, summary.kept, summary.unchanged),
+            (1, 0, 1, 1)
+        );
+    }
+
+    #[test]
+    fn ask_without_a_terminal_is_a_conflict_and_writes_nothing() {
+        // Test runs have no terminal on stdin
+        let (generated, existing) = generated_and_existing();
+
+        let result = apply_generated(generated.path(), existing.path(), OverwriteMode::Ask);
+
+        assert!(matches!(result, Err(DevgeiniError::Conflict(_))));
+        assert_eq!(result.err().unwrap().exit_code(), 7);
+        assert!(!existing.path().join("src").exists());
+        assert_eq!(fs::read_to_string(existing.path().join("README.md")).unwrap(), "# mine\n");
+    }
+
+    #[test]
+    fn ask_without_conflicts_needs_no_terminal() {
+        let (generated, existing) = generated_and_existing();
+        fs::write(existing.path().join("README.md"), "# generated\n").unwrap();
+
+        let summary = apply_generated(generated.path(), existing.path(), OverwriteMode::Ask).unwrap();
+
+        assert_eq!((summary.created, summary.unchanged), (1, 2));
+    }
+
+    #[test]
+    fn backup_moves_the_directory_next_to_itself() {
+        let parent = tempfile::tempdir().unwrap();
+        let project = parent.path().join("shop");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("README.md"), "# shop\n").unwrap();
+
+        let backup = backup_existing(&project).unwrap();
+
+        assert!(!project.exists());
+        assert_eq!(backup.parent(), Some(parent.path()));
+        let name = backup.file_name().unwrap().to_str().unwrap();
+        let timestamp = name.strip_prefix("shop.backup-").unwrap();
+        assert_eq!(timestamp.len(), "20240101-120000".len());
+        assert!(timestamp.chars().all(|c| c.is_ascii_digit() || c == '-'));
+        assert_eq!(fs::read_to_string(backup.join("README.md")).unwrap(), "# shop\n");
+    }
+
+    #[test]
+    fn backup_refuses_paths_without_a_name() {
+        for path in [".", ".."] {
+            assert!(matches!(backup_existing(Path::new(path)), Err(DevgeiniError::Conflict(_))));
+        }
+    }
+
+    #[test]
+    fn dirty_git_repositories_are_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| Command::new("git").arg("-C").arg(dir.path()).args(args).output();
+        if git(&["init", "--quiet"]).is_err() {
+            // Without git there is nothing to protect
+            return;
+        }
+
+        assert!(ensure_no_uncommitted_changes(dir.path()).is_ok());
+
+        fs::write(dir.path().join("notes.txt"), "draft\n").unwrap();
+        assert!(matches!(
+            ensure_no_uncommitted_changes(dir.path()),
+            Err(DevgeiniError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn directories_outside_git_are_accepted() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("notes.txt"), "draft\n").unwrap();
+
+        assert!(ensure_no_uncommitted_changes(dir.path()).is_ok());
+    }
+}
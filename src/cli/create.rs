@@ -0,0 +1,252 @@
+use colored::Colorize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use devgeini::{generate, ArchiveWriter, DevgeiniError, DiskWriter, MemoryWriter, ProjectConfig, ProjectWriter};
+
+use crate::cli::conflicts::{apply_generated, backup_existing, ensure_no_uncommitted_changes, OverwriteMode};
+use crate::cli::staging::StagingDir;
+
+/// Anything that can write a complete project, with paths relative to its root. It returns
+/// the lines its hooks printed, shown once generation is done.
+pub type Generator<'a> = &'a dyn Fn(&mut dyn ProjectWriter) -> Result<Vec<String>, DevgeiniError>;
+
+pub fn create_project(config: &ProjectConfig, mode: OverwriteMode) -> Result<(), DevgeiniError> {
+    create_project_with(&config.root, mode, &|writer| generate(config, writer).map(|report| report.hook_output))
+}
+
+/// Stages everything `generate` writes and moves it into `root` according to `mode`.
+pub fn create_project_with(root: &Path, mode: OverwriteMode, generate: Generator) -> Result<(), DevgeiniError> {
+    let project_path = resolve_root(root)?;
+    let project_path = project_path.as_path();
+
+    if project_path.exists() {
+        ensure_no_uncommitted_changes(project_path)?;
+    }
+
+    // Nothing touches the target until every generator has succeeded
+    let staging = StagingDir::new(project_path)?;
+    print_messages(&generate(&mut DiskWriter::new(staging.path()))?);
+
+    if !project_path.exists() {
+        staging.commit_to(project_path)?;
+    } else {
+        match mode {
+            OverwriteMode::Force => {
+                if env::current_dir().is_ok_and(|current| current == project_path) {
+                    return Err(DevgeiniError::Conflict(
+                        "refusing to move the current directory aside; use --merge to add files to it".to_string(),
+                    ));
+                }
+                let backup_path = backup_existing(project_path)?;
+                if let Err(e) = staging.commit_to(project_path) {
+                    // Put the original project back rather than leaving nothing behind
+                    fs::rename(&backup_path, project_path).map_err(|e| DevgeiniError::io(&backup_path, e))?;
+                    return Err(e);
+                }
+                println!("📦 Moved existing directory to {}", backup_path.display());
+            }
+            OverwriteMode::Ask | OverwriteMode::Merge => {
+                let summary = apply_generated(staging.path(), project_path, mode)?;
+                println!(
+                    "📝 {} created, {} overwritten, {} kept, {} unchanged",
+                    summary.created, summary.overwritten, summary.kept, summary.unchanged
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// `.` and `..` have no file name to stage or back up next to, so work with the absolute path
+fn resolve_root(root: &Path) -> Result<PathBuf, DevgeiniError> {
+    if root.file_name().is_some() {
+        return Ok(root.to_path_buf());
+    }
+    fs::canonicalize(root).map_err(|e| DevgeiniError::io(root, e))
+}
+
+/// Prints what `create_project` would generate without touching the disk.
+pub fn preview_project(config: &ProjectConfig, show_contents: bool) -> Result<(), DevgeiniError> {
+    preview_project_with(&config.root, show_contents, &|writer| generate(config, writer).map(|report| report.hook_output))
+}
+
+pub fn preview_project_with(root: &Path, show_contents: bool, generate: Generator) -> Result<(), DevgeiniError> {
+    let mut files = MemoryWriter::new();
+    let messages = generate(&mut files)?;
+
+    println!("🔍 Dry run: nothing will be written\n");
+    print_messages(&messages);
+    print_tree(&files, &root.display().to_string());
+    if show_contents {
+        print_contents(&files);
+    }
+
+    Ok(())
+}
+
+/// Prints the recorded layout as a tree, directories first, with file sizes.
+fn print_tree(files: &MemoryWriter, root_name: &str) {
+    println!("{}/", root_name.bold());
+    for line in tree_lines(files, Path::new(""), "") {
+        println!("{}", line);
+    }
+
+    let file_count = files.files().count();
+    let total_size: usize = files.files().map(|(_, contents)| contents.len()).sum();
+    println!("\n{} files, {}", file_count, format_size(total_size));
+}
+
+fn print_contents(files: &MemoryWriter) {
+    for (path, contents) in files.files() {
+        println!("\n{}", format!("── {} ──", path.display()).cyan().bold());
+        match std::str::from_utf8(contents) {
+            Ok(text) => println!("{}", text),
+            Err(_) => println!("(binary file, {})", format_size(contents.len())),
+        }
+    }
+}
+
+// The entries below `dir` drawn as tree branches, directories first
+fn tree_lines(files: &MemoryWriter, dir: &Path, prefix: &str) -> Vec<String> {
+    // (path, size) with no size for directories, which sort first
+    let mut children: Vec<(&Path, Option<usize>)> = files
+        .dirs()
+        .map(|path| (path, None))
+        .chain(files.files().map(|(path, contents)| (path, Some(contents.len()))))
+        .filter(|(path, _)| path.parent() == Some(dir))
+        .collect();
+    children.sort_by_key(|(path, size)| (size.is_some(), path.file_name()));
+
+    let mut lines = Vec::new();
+    for (i, (path, size)) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let branch = if last { "└── " } else { "├── " };
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        match size {
+            None => {
+                lines.push(format!("{}{}{}/", prefix, branch, name.blue().bold()));
+                let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+                lines.extend(tree_lines(files, path, &child_prefix));
+            }
+            Some(size) => {
+                lines.push(format!("{}{}{} {}", prefix, branch, name, format!("({})", format_size(*size)).dimmed()));
+            }
+        }
+    }
+    lines
+}
+
+fn format_size(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+// Output of the generation, such as what template hooks printed
+fn print_messages(messages: &[String]) {
+    for message in messages {
+        println!("🪝 {}", message);
+    }
+}
+
+/// Packs the generated project into a `.zip` or `.tar.gz` file instead of a directory.
+pub fn archive_project(config: &ProjectConfig, archive_path: &Path) -> Result<(), DevgeiniError> {
+    archive_project_with(archive_path, &config.name, &|writer| generate(config, writer).map(|report| report.hook_output))
+}
+
+pub fn archive_project_with(archive_path: &Path, root_name: &str, generate: Generator) -> Result<(), DevgeiniError> {
+    let mut archive = ArchiveWriter::create(archive_path, root_name).map_err(|e| DevgeiniError::io(archive_path, e))?;
+    print_messages(&generate(&mut archive)?);
+    archive.finish().map_err(|e| DevgeiniError::io(archive_path, e))?;
+
+    println!("📦 Wrote {}", archive_path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dry_run_tree_lists_directories_first() {
+        colored::control::set_override(false);
+        let mut files = MemoryWriter::new();
+        let writer: &mut dyn ProjectWriter = &mut files;
+        writer.write("README.md", "# shop\n").unwrap();
+        writer.write("src/main.rs", "fn main() {}\n").unwrap();
+        writer.write("src/bin/tool.rs", "").unwrap();
+        writer.create_dir_all("assets").unwrap();
+        writer.write(".gitignore", vec![b'x'; 2048]).unwrap();
+
+        assert_eq!(
+            tree_lines(&files, Path::new(""), ""),
+            [
+                "├── assets/",
+                "├── src/",
+                "│   ├── bin/",
+                "│   │   └── tool.rs (0 B)",
+                "│   └── main.rs (13 B)",
+                "├── .gitignore (2.0 KB)",
+                "└── README.md (7 B)",
+            ]
+        );
+    }
+
+    fn failing_generator(writer: &mut dyn ProjectWriter) -> Result<Vec<String>, DevgeiniError> {
+        writer.write("README.md", "# half done\n")?;
+        Err(DevgeiniError::Template("broken template".to_string()))
+    }
+
+    #[test]
+    fn failed_generation_leaves_nothing_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("apps").join("shop");
+
+        let result = create_project_with(&root, OverwriteMode::Ask, &failing_generator);
+
+        assert!(matches!(result, Err(DevgeiniError::Template(_))));
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn failed_generation_keeps_an_existing_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("shop");
+        fs::create_dir(&root).unwrap();
+        fs::write(root.join("README.md"), "# mine\n").unwrap();
+
+        assert!(create_project_with(&root, OverwriteMode::Force, &failing_generator).is_err());
+
+        assert_eq!(fs::read_to_string(root.join("README.md")).unwrap(), "# mine\n");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn output_dir_is_independent_of_the_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = ProjectConfig::new("my-shop".to_string(), devgeini::ProjectType::CliTool);
+        config.root = dir.path().join("tools").join("cli");
+
+        create_project(&config, OverwriteMode::Ask).unwrap();
+
+        let cargo_toml = fs::read_to_string(config.root.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"my-shop\""), "{}", cargo_toml);
+        assert!(!dir.path().join("my-shop").exists());
+    }
+
+    #[test]
+    fn dot_roots_resolve_to_a_named_directory() {
+        let root = resolve_root(Path::new(".")).unwrap();
+
+        assert!(root.is_absolute());
+        assert_eq!(root, env::current_dir().unwrap());
+        assert!(root.file_name().is_some());
+    }
+}
+
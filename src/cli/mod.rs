@@ -0,0 +1,6 @@
+pub mod conflicts;
+pub mod create;
+pub mod stacks;
+pub mod staging;
+pub mod update;
+pub mod wizard;
@@ -0,0 +1,67 @@
+use clap::builder::{PossibleValue, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::ValueEnum;
+use std::ffi::OsStr;
+use std::sync::OnceLock;
+
+use devgeini::{BackendStack, DevgeiniError, FrontendStack, StackRegistry};
+
+/// The built-in and plugin stacks. The plugins are asked for their stacks the first time this
+/// is called, which only happens for menus, `list` and stack ids devgeini does not know.
+pub fn plugin_registry() -> &'static StackRegistry {
+    static REGISTRY: OnceLock<StackRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(StackRegistry::with_plugins)
+}
+
+/// Runs `resolve` with only the built-in stacks, and once more with the plugins when it names
+/// a stack devgeini does not know itself, so plugins only start for their own stacks.
+pub fn with_stacks<T>(resolve: impl Fn(&StackRegistry) -> Result<T, DevgeiniError>) -> Result<T, DevgeiniError> {
+    match resolve(&StackRegistry::new()) {
+        Err(DevgeiniError::UnknownStack { .. }) => resolve(plugin_registry()),
+        result => result,
+    }
+}
+
+/// Parses `--frontend` and `--backend`, listing the built-in ids in `--help` like a `ValueEnum`.
+#[derive(Clone)]
+pub struct StackParser<T> {
+    resolve: fn(&StackRegistry, &str) -> Result<T, DevgeiniError>,
+}
+
+impl StackParser<FrontendStack> {
+    pub fn frontend() -> Self {
+        StackParser {
+            resolve: |stacks, id| stacks.frontend(id),
+        }
+    }
+}
+
+impl StackParser<BackendStack> {
+    pub fn backend() -> Self {
+        StackParser {
+            resolve: |stacks, id| stacks.backend(id),
+        }
+    }
+}
+
+impl<T: ValueEnum + Clone + Send + Sync + 'static> TypedValueParser for StackParser<T> {
+    type Value = T;
+
+    fn parse_ref(&self, cmd: &clap::Command, arg: Option<&clap::Arg>, value: &OsStr) -> Result<T, clap::Error> {
+        let arg = arg.map_or_else(|| "...".to_string(), ToString::to_string);
+        let Some(id) = value.to_str() else {
+            return Err(clap::Error::raw(ErrorKind::InvalidUtf8, format!("invalid UTF-8 in '{}'\n", arg)).with_cmd(cmd));
+        };
+        with_stacks(|stacks| (self.resolve)(stacks, id)).map_err(|e| {
+            clap::Error::raw(
+                ErrorKind::ValueValidation,
+                format!("invalid value '{}' for '{}': {}\n", id, arg, e),
+            )
+            .with_cmd(cmd)
+        })
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(T::value_variants().iter().filter_map(ValueEnum::to_possible_value)))
+    }
+}
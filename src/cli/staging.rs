@@ -0,0 +1,205 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use devgeini::DevgeiniError;
+
+// What to remove if the user presses Ctrl-C, in the order it was registered
+static CLEANUP: Mutex<Vec<Cleanup>> = Mutex::new(Vec::new());
+
+struct Cleanup {
+    path: PathBuf,
+    /// Directories that only held other registered paths are removed when empty, never recursively
+    recursive: bool,
+}
+
+/// Exits with code 130 on Ctrl-C after removing everything registered with `remove_on_interrupt`.
+/// Installed once at startup, before anything temporary is created.
+pub fn install_interrupt_handler() {
+    let _ = ctrlc::set_handler(|| {
+        let cleanup = std::mem::take(&mut *CLEANUP.lock().unwrap_or_else(|e| e.into_inner()));
+        for Cleanup { path, recursive } in cleanup.into_iter().rev() {
+            let _ = if recursive { fs::remove_dir_all(&path) } else { fs::remove_dir(&path) };
+        }
+        eprintln!("\n❌ Cancelled");
+        std::process::exit(130);
+    });
+}
+
+/// Removes the directory at `path` on Ctrl-C for as long as the returned guard lives.
+pub fn remove_on_interrupt(path: &Path) -> InterruptCleanup {
+    register(path, true)
+}
+
+fn register(path: &Path, recursive: bool) -> InterruptCleanup {
+    CLEANUP.lock().unwrap_or_else(|e| e.into_inner()).push(Cleanup {
+        path: path.to_path_buf(),
+        recursive,
+    });
+    InterruptCleanup { path: path.to_path_buf() }
+}
+
+/// Keeps a path registered for removal on Ctrl-C; dropping it only unregisters the path.
+pub struct InterruptCleanup {
+    path: PathBuf,
+}
+
+impl Drop for InterruptCleanup {
+    fn drop(&mut self) {
+        let mut cleanup = CLEANUP.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(index) = cleanup.iter().rposition(|entry| entry.path == self.path) {
+            cleanup.remove(index);
+        }
+    }
+}
+
+/// A temporary directory next to the project target that generators write into.
+///
+/// It is removed when dropped (or on Ctrl-C) unless it was moved into place with `commit_to`,
+/// together with any parent directories of the target that had to be created for it.
+pub struct StagingDir {
+    path: PathBuf,
+    /// Parents of the target created for the staging directory, outermost first
+    created_parents: Vec<PathBuf>,
+    committed: bool,
+    // Declared last so the paths stay registered until the directories are gone
+    interrupt_cleanup: Vec<InterruptCleanup>,
+}
+
+impl StagingDir {
+    pub fn new(target: &Path) -> Result<Self, DevgeiniError> {
+        let file_name = target
+            .file_name()
+            .ok_or_else(|| DevgeiniError::InvalidConfig(format!("invalid project path '{}'", target.display())))?
+            .to_string_lossy();
+
+        // Same parent directory as the target so the final rename stays on one filesystem
+        let path = target.with_file_name(format!(".{}.devgeini-staging-{}", file_name, std::process::id()));
+        if path.exists() {
+            fs::remove_dir_all(&path).map_err(|e| DevgeiniError::io(&path, e))?;
+        }
+
+        let mut staging = StagingDir {
+            path,
+            created_parents: Vec::new(),
+            committed: false,
+            interrupt_cleanup: Vec::new(),
+        };
+        // Each directory is created and registered one at a time, so dropping `staging` on an
+        // error removes exactly what was made so far
+        let missing_parents = staging
+            .path
+            .ancestors()
+            .skip(1)
+            .take_while(|parent| !parent.as_os_str().is_empty() && !parent.exists())
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+        for parent in missing_parents.into_iter().rev() {
+            fs::create_dir(&parent).map_err(|e| DevgeiniError::io(&parent, e))?;
+            staging.interrupt_cleanup.push(register(&parent, false));
+            staging.created_parents.push(parent);
+        }
+        fs::create_dir(&staging.path).map_err(|e| DevgeiniError::io(&staging.path, e))?;
+        staging.interrupt_cleanup.push(remove_on_interrupt(&staging.path));
+
+        Ok(staging)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Atomically renames the staged project to `target`, which must not exist.
+    pub fn commit_to(mut self, target: &Path) -> Result<(), DevgeiniError> {
+        // Renaming onto an empty directory would succeed on Unix and silently replace it
+        if target.exists() {
+            return Err(DevgeiniError::Conflict(format!("'{}' already exists", target.display())));
+        }
+        fs::rename(&self.path, target).map_err(|e| DevgeiniError::io(target, e))?;
+        self.committed = true;
+        Ok(())
+    }
+}
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        if self.path.exists() {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+        for parent in self.created_parents.iter().rev() {
+            let _ = fs::remove_dir(parent);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registered() -> Vec<PathBuf> {
+        CLEANUP.lock().unwrap().iter().map(|entry| entry.path.clone()).collect()
+    }
+
+    #[test]
+    fn commit_moves_the_staged_files_and_keeps_new_parents() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("apps").join("web").join("shop");
+
+        let staging = StagingDir::new(&target).unwrap();
+        let staging_path = staging.path().to_path_buf();
+        assert_eq!(staging_path.parent(), target.parent());
+        assert!(registered().contains(&staging_path));
+        fs::write(staging.path().join("README.md"), "# shop\n").unwrap();
+        staging.commit_to(&target).unwrap();
+
+        assert_eq!(fs::read_to_string(target.join("README.md")).unwrap(), "# shop\n");
+        assert!(!staging_path.exists());
+        assert!(!registered().contains(&staging_path));
+    }
+
+    #[test]
+    fn dropping_before_commit_removes_the_staging_dir_and_new_parents() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("apps").join("web").join("shop");
+
+        let staging = StagingDir::new(&target).unwrap();
+        fs::create_dir_all(staging.path().join("src")).unwrap();
+        fs::write(staging.path().join("src").join("main.rs"), "fn main() {}\n").unwrap();
+        drop(staging);
+
+        assert!(!dir.path().join("apps").exists());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn dropping_keeps_parents_that_already_existed_or_were_filled_meanwhile() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("apps")).unwrap();
+        let target = dir.path().join("apps").join("web").join("shop");
+
+        let staging = StagingDir::new(&target).unwrap();
+        fs::write(dir.path().join("apps").join("web").join("notes.txt"), "mine\n").unwrap();
+        drop(staging);
+
+        assert!(dir.path().join("apps").join("web").join("notes.txt").exists());
+        assert_eq!(fs::read_dir(dir.path().join("apps").join("web")).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn commit_refuses_an_existing_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("shop");
+
+        let staging = StagingDir::new(&target).unwrap();
+        let staging_path = staging.path().to_path_buf();
+        fs::write(staging.path().join("README.md"), "# generated\n").unwrap();
+        fs::create_dir(&target).unwrap();
+
+        assert!(matches!(staging.commit_to(&target), Err(DevgeiniError::Conflict(_))));
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 0);
+        assert!(!staging_path.exists());
+    }
+}
@@ -0,0 +1,278 @@
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use serde::Deserialize;
+use std::path::Path;
+
+use devgeini::DevgeiniError;
+
+use crate::cli::wizard::prompt_error;
+
+// GitHub API structures
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    body: String,
+    assets: Vec<GitHubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubAsset {
+    name: String,
+    browser_download_url: String,
+    size: u64,
+}
+
+const GITHUB_REPO: &str = "abhix2112/devgeini"; // Replace with your actual GitHub repo
+const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub async fn handle_update() -> Result<(), DevgeiniError> {
+    println!("🔍 Checking for updates...");
+    
+    let latest_release = get_latest_release().await?;
+    let latest_version = latest_release.tag_name.trim_start_matches('v');
+    
+    if version_compare(CURRENT_VERSION, latest_version) >= 0 {
+        println!("✅ You're already running the latest version ({})", CURRENT_VERSION);
+        return Ok(());
+    }
+    
+    println!("🆕 New version available: {} -> {}", CURRENT_VERSION, latest_version);
+    println!("📋 Release notes:\n{}", latest_release.body);
+    
+    let confirm = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Do you want to update now?")
+        .default(true)
+        .interact()
+        .map_err(prompt_error)?;
+    
+    if !confirm {
+        println!("Update cancelled.");
+        return Ok(());
+    }
+    
+    // Download and install update
+    download_and_install_update(&latest_release).await?;
+    
+    println!("✅ Successfully updated to version {}!", latest_version);
+    println!("🔄 Please restart your terminal or run 'devgeini --version' to verify the update.");
+    
+    Ok(())
+}
+
+pub async fn check_for_updates() -> Result<(), DevgeiniError> {
+    println!("🔍 Checking for updates...");
+    
+    let latest_release = get_latest_release().await?;
+    let latest_version = latest_release.tag_name.trim_start_matches('v');
+    
+    println!("📦 Current version: {}", CURRENT_VERSION);
+    println!("🆕 Latest version: {}", latest_version);
+    
+    if version_compare(CURRENT_VERSION, latest_version) < 0 {
+        println!("🎉 A new version is available!");
+        println!("📋 Release notes:\n{}", latest_release.body);
+        println!("🚀 Run 'devgeini --update' to update to the latest version.");
+    } else {
+        println!("✅ You're running the latest version!");
+    }
+    
+    Ok(())
+}
+
+pub async fn check_for_updates_silent() -> Result<(), DevgeiniError> {
+    let latest_release = get_latest_release().await?;
+    let latest_version = latest_release.tag_name.trim_start_matches('v');
+    
+    if version_compare(CURRENT_VERSION, latest_version) < 0 {
+        println!("💡 A new version ({}) is available! Run 'devgeini --update' to upgrade.", latest_version);
+    }
+    
+    Ok(())
+}
+
+async fn get_latest_release() -> Result<GitHubRelease, DevgeiniError> {
+    let client = reqwest::Client::new();
+    let url = format!("https://api.github.com/repos/{}/releases/latest", GITHUB_REPO);
+    
+    let response = client
+        .get(&url)
+        .header("User-Agent", format!("devgeini/{}", CURRENT_VERSION))
+        .send()
+        .await
+        .map_err(|e| DevgeiniError::Network(format!("could not reach GitHub: {}", e)))?;
+    
+    if !response.status().is_success() {
+        return Err(DevgeiniError::Network(format!("GitHub API request failed: {}", response.status())));
+    }
+    
+    let release: GitHubRelease = response
+        .json()
+        .await
+        .map_err(|e| DevgeiniError::Network(format!("unexpected answer from the GitHub API: {}", e)))?;
+    Ok(release)
+}
+
+async fn download_and_install_update(release: &GitHubRelease) -> Result<(), DevgeiniError> {
+    // Determine the correct asset for the current platform
+    let target_asset = find_matching_asset(&release.assets)?;
+    
+    println!("📥 Downloading {} ({} bytes)...", target_asset.name, target_asset.size);
+    
+    // Download the asset
+    let client = reqwest::Client::new();
+    let response = client
+        .get(&target_asset.browser_download_url)
+        .header("User-Agent", format!("devgeini/{}", CURRENT_VERSION))
+        .send()
+        .await
+        .map_err(|e| DevgeiniError::Network(format!("could not download {}: {}", target_asset.name, e)))?;
+    
+    if !response.status().is_success() {
+        return Err(DevgeiniError::Network(format!("Failed to download update: {}", response.status())));
+    }
+    
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| DevgeiniError::Network(format!("could not download {}: {}", target_asset.name, e)))?;
+    
+    // Get current executable path
+    let current_exe = std::env::current_exe().map_err(|e| DevgeiniError::io("<current executable>", e))?;
+    let backup_path = current_exe.with_extension("bak");
+    
+    // Create backup of current executable
+    std::fs::copy(&current_exe, &backup_path).map_err(|e| DevgeiniError::io(&backup_path, e))?;
+    println!("📁 Created backup at: {}", backup_path.display());
+    
+    // Write new executable
+    if target_asset.name.ends_with(".tar.gz") || target_asset.name.ends_with(".zip") {
+        // Handle compressed archives
+        extract_and_install_executable(&bytes, &current_exe, &target_asset.name).await?;
+    } else {
+        // Direct executable replacement
+        install_executable(&current_exe, &bytes)?;
+    }
+    
+    println!("✅ Installation completed!");
+    
+    // Clean up backup on successful installation
+    if let Err(e) = std::fs::remove_file(&backup_path) {
+        println!("⚠️  Warning: Could not remove backup file: {}", e);
+    }
+    
+    Ok(())
+}
+
+fn find_matching_asset(assets: &[GitHubAsset]) -> Result<&GitHubAsset, DevgeiniError> {
+    let target_os = std::env::consts::OS;
+    let target_arch = std::env::consts::ARCH;
+    
+    // Define platform-specific patterns
+    let patterns = match (target_os, target_arch) {
+        ("windows", "x86_64") => vec!["windows", "win64", "x86_64-pc-windows"],
+        ("windows", "x86") => vec!["windows", "win32", "i686-pc-windows"],
+        ("macos", "x86_64") => vec!["macos", "darwin", "x86_64-apple-darwin"],
+        ("macos", "aarch64") => vec!["macos", "darwin", "aarch64-apple-darwin"],
+        ("linux", "x86_64") => vec!["linux", "x86_64-unknown-linux"],
+        ("linux", "aarch64") => vec!["linux", "aarch64-unknown-linux"],
+        _ => vec!["universal"],
+    };
+    
+    // Find matching asset
+    for asset in assets {
+        let asset_name_lower = asset.name.to_lowercase();
+        for pattern in &patterns {
+            if asset_name_lower.contains(pattern) {
+                return Ok(asset);
+            }
+        }
+    }
+    
+    // Fallback to first asset if no specific match found
+    assets.first().ok_or_else(|| {
+        DevgeiniError::UpdateVerification(format!(
+            "No suitable release asset found for your platform ({}-{})",
+            target_os, target_arch
+        ))
+    })
+}
+
+async fn extract_and_install_executable(
+    bytes: &[u8], 
+    target_path: &Path, 
+    filename: &str
+) -> Result<(), DevgeiniError> {
+    use std::io::Read;
+    
+    if filename.ends_with(".tar.gz") {
+        // Handle tar.gz files
+        let tar = flate2::read::GzDecoder::new(bytes);
+        let mut archive = tar::Archive::new(tar);
+        
+        for entry in archive.entries().map_err(corrupt_archive)? {
+            let mut entry = entry.map_err(corrupt_archive)?;
+            let path = entry.path().map_err(corrupt_archive)?;
+            
+            // Look for the executable (usually named 'devgeini' or similar)
+            if path.file_name().and_then(|s| s.to_str()).is_some_and(|s| s.starts_with("devgeini")) {
+                let mut buffer = Vec::new();
+                entry.read_to_end(&mut buffer).map_err(corrupt_archive)?;
+                return install_executable(target_path, &buffer);
+            }
+        }
+    } else if filename.ends_with(".zip") {
+        // Handle zip files (Windows typically)
+        let reader = std::io::Cursor::new(bytes);
+        let mut archive = zip::ZipArchive::new(reader).map_err(corrupt_archive)?;
+        
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).map_err(corrupt_archive)?;
+            if file.name().ends_with(".exe") || file.name().ends_with("devgeini") {
+                let mut buffer = Vec::new();
+                std::io::copy(&mut file, &mut buffer).map_err(corrupt_archive)?;
+                std::fs::write(target_path, buffer).map_err(|e| DevgeiniError::io(target_path, e))?;
+                return Ok(());
+            }
+        }
+    }
+    
+    Err(DevgeiniError::UpdateVerification(format!("Could not find executable in {}", filename)))
+}
+
+fn corrupt_archive(e: impl std::fmt::Display) -> DevgeiniError {
+    DevgeiniError::UpdateVerification(format!("the downloaded archive is damaged: {}", e))
+}
+
+// Replaces the running executable and marks it executable on unix
+fn install_executable(target_path: &Path, bytes: &[u8]) -> Result<(), DevgeiniError> {
+    std::fs::write(target_path, bytes).map_err(|e| DevgeiniError::io(target_path, e))?;
+    
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut permissions = std::fs::metadata(target_path).map_err(|e| DevgeiniError::io(target_path, e))?.permissions();
+        permissions.set_mode(0o755);
+        std::fs::set_permissions(target_path, permissions).map_err(|e| DevgeiniError::io(target_path, e))?;
+    }
+    
+    Ok(())
+}
+
+fn version_compare(current: &str, latest: &str) -> i32 {
+    let current_parts: Vec<u32> = current.split('.').filter_map(|s| s.parse().ok()).collect();
+    let latest_parts: Vec<u32> = latest.split('.').filter_map(|s| s.parse().ok()).collect();
+    
+    let max_len = current_parts.len().max(latest_parts.len());
+    
+    for i in 0..max_len {
+        let current_part = current_parts.get(i).unwrap_or(&0);
+        let latest_part = latest_parts.get(i).unwrap_or(&0);
+        
+        match current_part.cmp(latest_part) {
+            std::cmp::Ordering::Less => return -1,
+            std::cmp::Ordering::Greater => return 1,
+            std::cmp::Ordering::Equal => continue,
+        }
+    }
+    
+    0
+}
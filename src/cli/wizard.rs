@@ -0,0 +1,297 @@
+use clap::ValueEnum;
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use std::io::{self, IsTerminal};
+use std::sync::Once;
+
+use devgeini::config::naming::validate_project_name;
+use devgeini::config::project_types::PROJECT_TYPES;
+use devgeini::config::user::{validate_preset_name, Preset, UserConfig};
+use devgeini::template::Prompt;
+use devgeini::{BackendStack, Database, DevgeiniError, FrontendStack, ProjectConfig, ProjectType, StackKind};
+
+use crate::cli::stacks::plugin_registry;
+
+pub fn get_project_name() -> Result<String, DevgeiniError> {
+    require_terminal("--name <NAME>")?;
+    Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Enter project name")
+        .validate_with(|input: &String| validate_project_name(input))
+        .interact_text()
+        .map_err(prompt_error)
+}
+
+pub fn get_project_config_interactive(name: String) -> Result<ProjectConfig, DevgeiniError> {
+    let project_type = select_project_type()?;
+    
+    let mut config = ProjectConfig::new(name, project_type);
+
+    match project_type {
+        ProjectType::FullStackWeb => {
+            config.frontend_stack = Some(select_frontend_stack()?);
+            config.backend_stack = Some(select_backend_stack()?);
+        }
+        ProjectType::Frontend => {
+            config.frontend_stack = Some(select_frontend_stack()?);
+        }
+        ProjectType::Backend => {
+            config.backend_stack = Some(select_backend_stack()?);
+        }
+        _ => {}
+    }
+    if project_type.has_backend() {
+        config.database = select_database()?;
+    }
+
+    offer_to_save_preset(&config)?;
+    Ok(config)
+}
+
+// Only the wizard asks; flag and preset runs already say exactly what they want
+fn offer_to_save_preset(config: &ProjectConfig) -> Result<(), DevgeiniError> {
+    let save = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Save these choices as a preset?")
+        .default(false)
+        .interact()
+        .map_err(prompt_error)?;
+    if !save {
+        return Ok(());
+    }
+
+    let user_config = match UserConfig::load() {
+        Ok(user_config) => user_config,
+        Err(e) => {
+            println!("⚠️  Could not save the preset: {}", e);
+            return Ok(());
+        }
+    };
+
+    let name: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Preset name")
+        .validate_with(|input: &String| validate_preset_name(input))
+        .interact_text()
+        .map_err(prompt_error)?;
+
+    if user_config.presets.contains_key(&name) {
+        let replace = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Replace the existing preset '{}'?", name))
+            .default(false)
+            .interact()
+            .map_err(prompt_error)?;
+        if !replace {
+            return Ok(());
+        }
+    }
+
+    match UserConfig::save_preset(&name, &Preset::from_config(config)) {
+        Ok(path) => println!("💾 Saved preset '{}' to {} (use it with: devgeini init --preset {})", name, path.display(), name),
+        Err(e) => println!("⚠️  Could not save the preset: {}", e),
+    }
+    Ok(())
+}
+
+/// Builds a config from `init` flags, only prompting for the pieces that were not given.
+pub fn get_project_config_from_flags(
+    name: String,
+    project_type: Option<ProjectType>,
+    frontend_stack: Option<FrontendStack>,
+    backend_stack: Option<BackendStack>,
+    database: Option<Database>,
+) -> Result<ProjectConfig, DevgeiniError> {
+    // Infer the project type from the stacks when --type is omitted
+    let project_type = match (project_type, &frontend_stack, &backend_stack) {
+        (Some(project_type), _, _) => project_type,
+        (None, Some(_), Some(_)) => ProjectType::FullStackWeb,
+        (None, Some(_), None) => ProjectType::Frontend,
+        (None, None, Some(_)) => ProjectType::Backend,
+        (None, None, None) => select_project_type()?,
+    };
+
+    let mut config = ProjectConfig::new(name, project_type);
+    config.frontend_stack = frontend_stack;
+    config.backend_stack = backend_stack;
+    config.database = database;
+
+    if project_type.has_frontend() && config.frontend_stack.is_none() {
+        config.frontend_stack = Some(select_frontend_stack()?);
+    }
+    if project_type.has_backend() && config.backend_stack.is_none() {
+        config.backend_stack = Some(select_backend_stack()?);
+    }
+
+    config.validate()?;
+    Ok(config)
+}
+
+pub fn select_project_type() -> Result<ProjectType, DevgeiniError> {
+    require_terminal("--type <PROJECT_TYPE>")?;
+    let labels: Vec<&str> = PROJECT_TYPES.iter().map(|info| info.display_name).collect();
+
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Select project type")
+        .items(&labels)
+        .default(0)
+        .interact()
+        .map_err(prompt_error)?;
+
+    Ok(PROJECT_TYPES[selection].project_type)
+}
+
+pub fn select_frontend_stack() -> Result<FrontendStack, DevgeiniError> {
+    plugin_registry().frontend(select_stack(StackKind::Frontend, "Select frontend technology")?)
+}
+
+pub fn select_backend_stack() -> Result<BackendStack, DevgeiniError> {
+    plugin_registry().backend(select_stack(StackKind::Backend, "Select backend technology")?)
+}
+
+// The database is optional, so only the wizard asks for it
+pub fn select_database() -> Result<Option<Database>, DevgeiniError> {
+    let databases = Database::value_variants();
+    let mut labels = vec!["None".to_string()];
+    labels.extend(databases.iter().map(|database| database.to_string()));
+
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Select database")
+        .items(&labels)
+        .default(0)
+        .interact()
+        .map_err(prompt_error)?;
+
+    Ok(selection.checked_sub(1).map(|index| databases[index]))
+}
+
+// Offers every registered stack of `kind` and returns the chosen id
+fn select_stack(kind: StackKind, prompt: &str) -> Result<&'static str, DevgeiniError> {
+    require_terminal(&format!("--{} <STACK> (see 'devgeini list')", kind.dir()))?;
+    warn_about_skipped_plugins();
+    let stacks: Vec<_> = plugin_registry().of_kind(kind).collect();
+    let labels: Vec<&str> = stacks.iter().map(|stack| stack.display_name()).collect();
+
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(&labels)
+        .default(0)
+        .interact()
+        .map_err(prompt_error)?;
+
+    Ok(stacks[selection].id())
+}
+
+// Once per run, before the first menu that lists plugin stacks
+fn warn_about_skipped_plugins() {
+    static WARNED: Once = Once::new();
+    WARNED.call_once(|| {
+        for warning in plugin_registry().warnings() {
+            eprintln!("⚠️  {}", warning);
+        }
+    });
+}
+
+// Template prompts, asked when `init --template` runs in a terminal
+pub fn ask_template_prompt(prompt: &Prompt, default: Option<toml::Value>) -> Result<toml::Value, DevgeiniError> {
+    let theme = ColorfulTheme::default();
+
+    if !prompt.choices.is_empty() {
+        let default_index = default
+            .as_ref()
+            .and_then(|d| d.as_str())
+            .and_then(|d| prompt.choices.iter().position(|choice| choice == d))
+            .unwrap_or(0);
+        let selection = Select::with_theme(&theme)
+            .with_prompt(prompt.text())
+            .items(&prompt.choices)
+            .default(default_index)
+            .interact()
+            .map_err(prompt_error)?;
+        return Ok(toml::Value::String(prompt.choices[selection].clone()));
+    }
+
+    if let Some(toml::Value::Boolean(default)) = default {
+        let answer = Confirm::with_theme(&theme)
+            .with_prompt(prompt.text())
+            .default(default)
+            .interact()
+            .map_err(prompt_error)?;
+        return Ok(toml::Value::Boolean(answer));
+    }
+
+    let mut input = Input::<String>::with_theme(&theme).with_prompt(prompt.text());
+    if let Some(default) = default {
+        let text = match default {
+            toml::Value::String(text) => text,
+            other => other.to_string(),
+        };
+        input = input.default(text);
+    }
+    Ok(toml::Value::String(input.interact_text().map_err(prompt_error)?))
+}
+
+// Scripts and CI have nobody to answer prompts, so name the flag that is missing instead
+fn require_terminal(flag: &str) -> Result<(), DevgeiniError> {
+    if io::stdin().is_terminal() {
+        return Ok(());
+    }
+    Err(DevgeiniError::InvalidConfig(format!(
+        "not running in a terminal, so nothing can be asked; pass {}",
+        flag
+    )))
+}
+
+/// Converts a failed prompt; Ctrl-C while it is shown counts as cancelling.
+pub fn prompt_error(error: dialoguer::Error) -> DevgeiniError {
+    let dialoguer::Error::IO(source) = error;
+    if source.kind() == io::ErrorKind::Interrupted {
+        DevgeiniError::UserAborted
+    } else {
+        DevgeiniError::io("<terminal>", source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_flags(
+        project_type: Option<ProjectType>,
+        frontend: Option<&str>,
+        backend: Option<&str>,
+    ) -> Result<ProjectConfig, DevgeiniError> {
+        let frontend = frontend.map(|id| id.parse().unwrap());
+        let backend = backend.map(|id| id.parse().unwrap());
+        get_project_config_from_flags("shop".to_string(), project_type, frontend, backend, None)
+    }
+
+    #[test]
+    fn project_type_is_inferred_from_the_stacks() {
+        let cases = [
+            (Some("react-ts"), Some("go"), ProjectType::FullStackWeb),
+            (Some("vue"), None, ProjectType::Frontend),
+            (None, Some("python"), ProjectType::Backend),
+        ];
+        for (frontend, backend, expected) in cases {
+            assert_eq!(from_flags(None, frontend, backend).unwrap().project_type, expected);
+        }
+    }
+
+    #[test]
+    fn stacks_that_do_not_fit_the_type_are_unsupported() {
+        let error = from_flags(Some(ProjectType::Backend), Some("react"), Some("go")).unwrap_err();
+
+        assert!(matches!(error, DevgeiniError::UnsupportedStack { .. }), "{:?}", error);
+        assert_eq!(error.exit_code(), 3);
+    }
+
+    #[test]
+    fn a_database_needs_a_backend() {
+        let error = get_project_config_from_flags(
+            "shop".to_string(),
+            None,
+            Some("react".parse().unwrap()),
+            None,
+            Some(Database::Postgres),
+        )
+        .unwrap_err();
+
+        assert!(matches!(error, DevgeiniError::InvalidConfig(_)), "{:?}", error);
+    }
+}
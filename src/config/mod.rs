@@ -1 +1,4 @@
 pub mod structure;
+pub mod project_types;
+pub mod naming;
+pub mod user;
@@ -0,0 +1,176 @@
+use serde::Serialize;
+
+/// The project name rendered for each ecosystem it ends up in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProjectIdentifiers {
+    /// npm packages, Cargo crates, Go modules, Maven artifacts: `my-app`
+    pub kebab: String,
+    /// Python modules and Rust identifiers: `my_app`
+    pub snake: String,
+    /// Class and component names: `MyApp`
+    pub pascal: String,
+    /// Java packages: `com.example.myapp`
+    pub package: String,
+}
+
+impl ProjectIdentifiers {
+    pub fn from_name(name: &str) -> Self {
+        let words: Vec<String> = name
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_ascii_lowercase())
+            .collect();
+
+        let pascal = words
+            .iter()
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect();
+
+        ProjectIdentifiers {
+            kebab: words.join("-"),
+            snake: words.join("_"),
+            pascal,
+            package: format!("com.example.{}", words.concat()),
+        }
+    }
+}
+
+const MAX_NAME_LENGTH: usize = 214;
+
+// Names npm, Cargo or Windows refuse outright
+const RESERVED_NAMES: &[&str] = &[
+    "node_modules", "favicon.ico", "test", "std", "core", "alloc", "proc_macro", "build", "src",
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "false", "none", "true", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+const JAVA_KEYWORDS: &[&str] = &[
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const",
+    "continue", "default", "do", "double", "else", "enum", "extends", "final", "finally", "float",
+    "for", "goto", "if", "implements", "import", "instanceof", "int", "interface", "long",
+    "native", "new", "package", "private", "protected", "public", "return", "short", "static",
+    "strictfp", "super", "switch", "synchronized", "this", "throw", "throws", "transient", "try",
+    "void", "volatile", "while", "true", "false", "null",
+];
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+/// Checks that `name` works as a directory, an npm package, a Cargo crate, a Python module
+/// and a Java package segment, explaining what to change when it does not.
+pub fn validate_project_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("project name cannot be empty".to_string());
+    }
+    if name.contains('/') || name.contains('\\') {
+        return Err(format!(
+            "project name '{}' cannot contain path separators; use --output-dir to choose where it is generated",
+            name
+        ));
+    }
+    if name == "." || name.contains("..") {
+        return Err(format!("project name '{}' cannot be '.' or contain '..'", name));
+    }
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(format!("project name is {} characters long, the limit is {}", name.len(), MAX_NAME_LENGTH));
+    }
+
+    let identifiers = ProjectIdentifiers::from_name(name);
+    if name.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err(format!(
+            "npm package names must be lowercase, try '{}'",
+            identifiers.kebab
+        ));
+    }
+    if let Some(c) = name.chars().find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))) {
+        return Err(format!(
+            "project name cannot contain '{}', only lowercase letters, digits, '-', '_' and '.' are allowed (try '{}')",
+            c, identifiers.kebab
+        ));
+    }
+    if !name.starts_with(|c: char| c.is_ascii_lowercase()) {
+        return Err(format!(
+            "project name '{}' must start with a letter so it is a valid Python module and Java package",
+            name
+        ));
+    }
+    if RESERVED_NAMES.contains(&name) {
+        return Err(format!("'{}' is a reserved name, pick something more specific", name));
+    }
+
+    // The derived identifiers have to be valid in their own languages too
+    if PYTHON_KEYWORDS.contains(&identifiers.snake.as_str()) {
+        return Err(format!("'{}' is a Python keyword and cannot be used as a module name", identifiers.snake));
+    }
+    if RUST_KEYWORDS.contains(&identifiers.snake.as_str()) {
+        return Err(format!("'{}' is a Rust keyword and cannot be used as a crate name", identifiers.snake));
+    }
+    let package_segment = identifiers.package.rsplit('.').next().unwrap_or_default();
+    if JAVA_KEYWORDS.contains(&package_segment) {
+        return Err(format!(
+            "'{}' is a Java keyword and cannot be used in the package name '{}'",
+            package_segment, identifiers.package
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_follow_each_ecosystem() {
+        let identifiers = ProjectIdentifiers::from_name("my-app.v2_beta");
+        assert_eq!(identifiers.kebab, "my-app-v2-beta");
+        assert_eq!(identifiers.snake, "my_app_v2_beta");
+        assert_eq!(identifiers.pascal, "MyAppV2Beta");
+        assert_eq!(identifiers.package, "com.example.myappv2beta");
+    }
+
+    #[test]
+    fn accepts_usual_names() {
+        for name in ["shop", "my-app", "my_app", "api.v2", "a1"] {
+            assert_eq!(validate_project_name(name), Ok(()), "{}", name);
+        }
+    }
+
+    #[test]
+    fn rejects_names_that_break_an_ecosystem() {
+        let cases = [
+            ("", "cannot be empty"),
+            ("../app", "path separators"),
+            ("a..b", "contain '..'"),
+            ("MyApp", "try 'myapp'"),
+            ("my app", "cannot contain ' '"),
+            ("1app", "must start with a letter"),
+            ("node_modules", "reserved name"),
+            ("lambda", "Python keyword"),
+            ("crate", "Rust keyword"),
+            ("goto", "Java keyword"),
+        ];
+        for (name, expected) in cases {
+            let error = validate_project_name(name).expect_err(name);
+            assert!(error.contains(expected), "'{}': {}", name, error);
+        }
+        assert!(validate_project_name(&"a".repeat(MAX_NAME_LENGTH + 1)).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+use crate::config::structure::ProjectType;
+use serde::Serialize;
+
+/// How a project type is shown in the type menu and in `devgeini list`.
+#[derive(Serialize)]
+pub struct ProjectTypeInfo {
+    pub project_type: ProjectType,
+    pub display_name: &'static str,
+    pub description: &'static str,
+    pub language: &'static str,
+}
+
+// Same order as the type menu, the first entry is the default choice
+pub const PROJECT_TYPES: &[ProjectTypeInfo] = &[
+    ProjectTypeInfo {
+        project_type: ProjectType::FullStackWeb,
+        display_name: "Full Stack Web Application",
+        description: "A frontend and a backend stack in one repository with docker-compose",
+        language: "Any",
+    },
+    ProjectTypeInfo {
+        project_type: ProjectType::Frontend,
+        display_name: "Frontend Only",
+        description: "A single frontend stack with a production Dockerfile",
+        language: "JavaScript/TypeScript",
+    },
+    ProjectTypeInfo {
+        project_type: ProjectType::Backend,
+        display_name: "Backend Only",
+        description: "A single backend stack with a Dockerfile",
+        language: "Any",
+    },
+    ProjectTypeInfo {
+        project_type: ProjectType::CliTool,
+        display_name: "CLI Tool",
+        description: "Command-line tool built on clap",
+        language: "Rust",
+    },
+    ProjectTypeInfo {
+        project_type: ProjectType::WebExtension,
+        display_name: "Web Extension",
+        description: "Manifest V3 browser extension with popup, background and content scripts",
+        language: "JavaScript",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn type_menu_lists_every_project_type_once() {
+        let menu: Vec<ProjectType> = PROJECT_TYPES.iter().map(|info| info.project_type).collect();
+
+        assert_eq!(menu, ProjectType::value_variants());
+    }
+}
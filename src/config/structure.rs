@@ -1,42 +1,445 @@
-#[derive(Debug, Clone)]
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+use crate::config::naming::{validate_project_name, ProjectIdentifiers};
+use crate::error::DevgeiniError;
+use crate::stacks::builtin::builtin_stack;
+use crate::stacks::registry::StackRegistry;
+use crate::stacks::{StackGenerator, StackKind};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum ProjectType {
+    #[value(name = "fullstack")]
     FullStackWeb,
     Frontend,
     Backend,
+    #[value(name = "cli")]
     CliTool,
+    #[value(name = "extension")]
     WebExtension,
 }
 
-#[derive(Debug, Clone)]
+/// The database a fullstack or backend project is set up for, in `.env` and docker-compose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Database {
+    Postgres,
+    Mysql,
+    Mongodb,
+}
+
+/// A frontend stack. Parse a built-in one from its id, e.g. `react-ts`; other stacks come
+/// from a `StackRegistry`.
+#[derive(Clone)]
 pub enum FrontendStack {
-    React,
     ReactTs,
-    Vue,
+    React,
     VueTs,
+    Vue,
     Angular,
-    Svelte,
     SvelteTs,
-    NextJs,
-    NextJsTs,
-    Vanilla,
+    Svelte,
+    NextjsTs,
+    Nextjs,
     VanillaTs,
+    Vanilla,
+    /// A stack added to a `StackRegistry`, such as one of a `devgeini-plugin-*` executable
+    Plugin(Arc<dyn StackGenerator>),
 }
 
-#[derive(Debug, Clone)]
+/// A backend stack. Parse a built-in one from its id, e.g. `go`; other stacks come
+/// from a `StackRegistry`.
+#[derive(Clone)]
 pub enum BackendStack {
-    NodeJs,
-    NodeJsTs,
+    NodeTs,
+    Node,
     Python,
     Rust,
     Go,
     Java,
+    JavaGradle,
     Php,
-    
+    /// A stack added to a `StackRegistry`, such as one of a `devgeini-plugin-*` executable
+    Plugin(Arc<dyn StackGenerator>),
+}
+
+impl FrontendStack {
+    pub const BUILTIN: &'static [FrontendStack] = &[
+        FrontendStack::ReactTs,
+        FrontendStack::React,
+        FrontendStack::VueTs,
+        FrontendStack::Vue,
+        FrontendStack::Angular,
+        FrontendStack::SvelteTs,
+        FrontendStack::Svelte,
+        FrontendStack::NextjsTs,
+        FrontendStack::Nextjs,
+        FrontendStack::VanillaTs,
+        FrontendStack::Vanilla,
+    ];
+
+    fn builtin_id(&self) -> Option<&'static str> {
+        Some(match self {
+            FrontendStack::ReactTs => "react-ts",
+            FrontendStack::React => "react",
+            FrontendStack::VueTs => "vue-ts",
+            FrontendStack::Vue => "vue",
+            FrontendStack::Angular => "angular",
+            FrontendStack::SvelteTs => "svelte-ts",
+            FrontendStack::Svelte => "svelte",
+            FrontendStack::NextjsTs => "nextjs-ts",
+            FrontendStack::Nextjs => "nextjs",
+            FrontendStack::VanillaTs => "vanilla-ts",
+            FrontendStack::Vanilla => "vanilla",
+            FrontendStack::Plugin(_) => return None,
+        })
+    }
+}
+
+impl BackendStack {
+    pub const BUILTIN: &'static [BackendStack] = &[
+        BackendStack::NodeTs,
+        BackendStack::Node,
+        BackendStack::Python,
+        BackendStack::Rust,
+        BackendStack::Go,
+        BackendStack::Java,
+        BackendStack::JavaGradle,
+        BackendStack::Php,
+    ];
+
+    fn builtin_id(&self) -> Option<&'static str> {
+        Some(match self {
+            BackendStack::NodeTs => "node-ts",
+            BackendStack::Node => "node",
+            BackendStack::Python => "python",
+            BackendStack::Rust => "rust",
+            BackendStack::Go => "go",
+            BackendStack::Java => "java",
+            BackendStack::JavaGradle => "java-gradle",
+            BackendStack::Php => "php",
+            BackendStack::Plugin(_) => return None,
+        })
+    }
 }
 
+// Built-in variants are backed by the built-in stack table, the others carry their generator
+macro_rules! impl_stack {
+    ($($ty:ident => $kind:expr),*) => {
+        $(
+            impl $ty {
+                pub fn id(&self) -> &str {
+                    match self {
+                        $ty::Plugin(stack) => stack.id(),
+                        builtin => builtin.builtin_id().expect("only plugin stacks have no built-in id"),
+                    }
+                }
+
+                pub fn generator(&self) -> &dyn StackGenerator {
+                    match self {
+                        $ty::Plugin(stack) => stack.as_ref(),
+                        _ => builtin_stack($kind, self.id()).expect("every built-in variant has a built-in stack"),
+                    }
+                }
+            }
+
+            impl ValueEnum for $ty {
+                fn value_variants<'a>() -> &'a [Self] {
+                    $ty::BUILTIN
+                }
+
+                fn to_possible_value(&self) -> Option<PossibleValue> {
+                    self.builtin_id().map(PossibleValue::new)
+                }
+            }
+
+            // Only built-in ids parse, the `StackRegistry` resolves the others
+            impl FromStr for $ty {
+                type Err = DevgeiniError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    <$ty as ValueEnum>::from_str(s, true).map_err(|_| DevgeiniError::UnknownStack {
+                        kind: $kind,
+                        id: s.to_string(),
+                    })
+                }
+            }
+
+            // Stacks with the same id are the same stack, as ids are unique in a registry
+            impl PartialEq for $ty {
+                fn eq(&self, other: &Self) -> bool {
+                    self.id() == other.id()
+                }
+            }
+
+            impl Eq for $ty {}
+
+            impl fmt::Debug for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    match self {
+                        $ty::Plugin(stack) => f.debug_tuple("Plugin").field(&stack.id()).finish(),
+                        builtin => f.write_str(builtin.id()),
+                    }
+                }
+            }
+
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str(self.id())
+                }
+            }
+
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(self.id())
+                }
+            }
+        )*
+    };
+}
+
+impl_stack!(FrontendStack => StackKind::Frontend, BackendStack => StackKind::Backend);
+
+// Shared FromStr/Display/serde so the enum round-trips through the same names clap accepts
+macro_rules! impl_value_enum_str {
+    ($($ty:ty),*) => {
+        $(
+            impl FromStr for $ty {
+                type Err = String;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    <$ty as ValueEnum>::from_str(s, true).map_err(|_| {
+                        let valid: Vec<String> = <$ty as ValueEnum>::value_variants()
+                            .iter()
+                            .map(|v| v.to_string())
+                            .collect();
+                        format!("invalid value '{}' (expected one of: {})", s, valid.join(", "))
+                    })
+                }
+            }
+
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    let value = self.to_possible_value().expect("no skipped variants");
+                    f.write_str(value.get_name())
+                }
+            }
+
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let value = String::deserialize(deserializer)?;
+                    value.parse().map_err(serde::de::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+impl_value_enum_str!(ProjectType, Database);
+
+impl ProjectType {
+    pub fn has_frontend(&self) -> bool {
+        matches!(self, ProjectType::FullStackWeb | ProjectType::Frontend)
+    }
+
+    pub fn has_backend(&self) -> bool {
+        matches!(self, ProjectType::FullStackWeb | ProjectType::Backend)
+    }
+}
+
+impl Database {
+    /// Connection string for `db_name` on `host`, with the credentials the docker-compose service uses.
+    pub fn url(&self, host: &str, db_name: &str) -> String {
+        match self {
+            Database::Postgres => format!("postgresql://user:password@{}:5432/{}", host, db_name),
+            Database::Mysql => format!("mysql://user:password@{}:3306/{}", host, db_name),
+            Database::Mongodb => format!("mongodb://{}:27017/{}", host, db_name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct ProjectConfig {
+    #[serde(rename = "project_name")]
     pub name: String,
     pub project_type: ProjectType,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub frontend_stack: Option<FrontendStack>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub backend_stack: Option<BackendStack>,
-}
\ No newline at end of file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<Database>,
+    /// Directory the project is generated into. Not saved, so a config can be
+    /// regenerated anywhere; defaults to a directory named after the project.
+    #[serde(skip)]
+    pub root: PathBuf,
+    /// Derived from `name` by `set_name`
+    #[serde(skip)]
+    pub identifiers: ProjectIdentifiers,
+    /// Extra template variables, set by a pre-generation hook. They take precedence over
+    /// the built-in ones, so a hook can e.g. pick a different `api_port`.
+    #[serde(skip)]
+    pub variables: BTreeMap<String, toml::Value>,
+}
+
+// What `project_config.toml` holds; stack ids are resolved against a registry when loading
+#[derive(Deserialize)]
+struct SavedConfig {
+    project_name: String,
+    project_type: ProjectType,
+    frontend_stack: Option<String>,
+    backend_stack: Option<String>,
+    database: Option<Database>,
+}
+
+impl ProjectConfig {
+    pub fn new(name: String, project_type: ProjectType) -> Self {
+        let mut config = ProjectConfig {
+            name: String::new(),
+            project_type,
+            frontend_stack: None,
+            backend_stack: None,
+            database: None,
+            root: PathBuf::new(),
+            identifiers: ProjectIdentifiers::default(),
+            variables: BTreeMap::new(),
+        };
+        config.set_name(name);
+        config
+    }
+
+    /// Renames the project, which also moves its default root and re-derives its identifiers.
+    pub fn set_name(&mut self, name: String) {
+        self.root = PathBuf::from(&name);
+        self.identifiers = ProjectIdentifiers::from_name(&name);
+        self.name = name;
+    }
+
+    /// Reads a `project_config.toml` and checks that it describes a valid project.
+    ///
+    /// Stacks that are not built in are looked up in `registry`; an unknown one is an
+    /// `UnknownStack` error, so the caller can retry with the plugins loaded.
+    pub fn load(path: &Path, registry: &StackRegistry) -> Result<Self, DevgeiniError> {
+        let contents = fs::read_to_string(path).map_err(|e| DevgeiniError::io(path, e))?;
+        let saved: SavedConfig = toml::from_str(&contents)
+            .map_err(|e| DevgeiniError::InvalidConfig(format!("could not parse {}: {}", path.display(), e)))?;
+
+        let mut config = ProjectConfig::new(saved.project_name, saved.project_type);
+        config.frontend_stack = saved.frontend_stack.map(|id| registry.frontend(&id)).transpose()?;
+        config.backend_stack = saved.backend_stack.map(|id| registry.backend(&id)).transpose()?;
+        config.database = saved.database;
+        config.validate().map_err(|e| match e {
+            DevgeiniError::InvalidConfig(message) => {
+                DevgeiniError::InvalidConfig(format!("{} (in {})", message, path.display()))
+            }
+            other => other,
+        })?;
+        Ok(config)
+    }
+
+    /// The generators of the selected stacks, backend first.
+    pub fn stacks(&self) -> Vec<&dyn StackGenerator> {
+        let backend = self.backend_stack.as_ref().map(BackendStack::generator);
+        let frontend = self.frontend_stack.as_ref().map(FrontendStack::generator);
+        backend.into_iter().chain(frontend).collect()
+    }
+
+    /// Checks the project name and that the selected stacks make sense for the project type.
+    pub fn validate(&self) -> Result<(), DevgeiniError> {
+        validate_project_name(&self.name).map_err(DevgeiniError::InvalidConfig)?;
+
+        let project_type = self.project_type;
+
+        if let Some(frontend) = &self.frontend_stack {
+            if !frontend.generator().project_types().contains(&project_type) {
+                return Err(DevgeiniError::UnsupportedStack {
+                    stack: frontend.to_string(),
+                    project_type,
+                });
+            }
+        } else if project_type.has_frontend() {
+            return Err(DevgeiniError::InvalidConfig(format!(
+                "a '{}' project requires a frontend stack (pass --frontend)",
+                project_type
+            )));
+        }
+
+        if let Some(backend) = &self.backend_stack {
+            if !backend.generator().project_types().contains(&project_type) {
+                return Err(DevgeiniError::UnsupportedStack {
+                    stack: backend.to_string(),
+                    project_type,
+                });
+            }
+        } else if project_type.has_backend() {
+            return Err(DevgeiniError::InvalidConfig(format!(
+                "a '{}' project requires a backend stack (pass --backend)",
+                project_type
+            )));
+        }
+
+        if self.database.is_some() && !project_type.has_backend() {
+            return Err(DevgeiniError::InvalidConfig(format!(
+                "a '{}' project has no backend to use a database (drop --database)",
+                project_type
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::writer::MemoryWriter;
+
+    #[test]
+    fn project_config_file_regenerates_the_same_project() {
+        let mut config = ProjectConfig::new("my-shop".to_string(), ProjectType::FullStackWeb);
+        config.frontend_stack = Some("svelte-ts".parse().unwrap());
+        config.backend_stack = Some("java-gradle".parse().unwrap());
+        config.database = Some(Database::Mysql);
+
+        let mut writer = MemoryWriter::new();
+        crate::generate(&config, &mut writer).unwrap();
+        let (_, contents) = writer.files().find(|(path, _)| *path == Path::new("project_config.toml")).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("project_config.toml");
+        fs::write(&path, contents).unwrap();
+
+        let loaded = ProjectConfig::load(&path, &StackRegistry::new()).unwrap();
+        assert_eq!(loaded.name, "my-shop");
+        assert_eq!(loaded.project_type, ProjectType::FullStackWeb);
+        assert_eq!(loaded.frontend_stack, config.frontend_stack);
+        assert_eq!(loaded.backend_stack, config.backend_stack);
+        assert_eq!(loaded.database, Some(Database::Mysql));
+        assert_eq!(loaded.root, Path::new("my-shop"));
+    }
+
+    #[test]
+    fn invalid_project_config_files_name_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("project_config.toml");
+        fs::write(&path, "project_name = \"shop\"\nproject_type = \"fullstack\"\nbackend_stack = \"go\"\n").unwrap();
+
+        let error = ProjectConfig::load(&path, &StackRegistry::new()).unwrap_err();
+        assert!(matches!(error, DevgeiniError::InvalidConfig(_)), "{:?}", error);
+        assert!(error.to_string().contains(&path.display().to_string()), "{}", error);
+
+        fs::write(&path, "project_name = \"shop\"\nproject_type = \"backend\"\nbackend_stack = \"cobol\"\n").unwrap();
+        let error = ProjectConfig::load(&path, &StackRegistry::new()).unwrap_err();
+        assert!(matches!(error, DevgeiniError::UnknownStack { .. }), "{:?}", error);
+    }
+}
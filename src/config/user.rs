@@ -0,0 +1,350 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Table};
+
+use crate::config::structure::{BackendStack, Database, FrontendStack, ProjectConfig, ProjectType};
+use crate::error::DevgeiniError;
+use crate::stacks::registry::StackRegistry;
+
+const CONFIG_HEADER: &str = "# devgeini user settings. Use a preset with: devgeini init --preset <name>\n";
+
+/// Per-user settings, read from `~/.config/devgeini/config.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UserConfig {
+    #[serde(default)]
+    pub presets: BTreeMap<String, Preset>,
+}
+
+/// Saved `init` choices, either a built-in stack combination or a project template
+/// together with its answers (database, Docker, CI and other integrations):
+///
+/// ```toml
+/// [presets.team-web]
+/// project_type = "fullstack"
+/// frontend_stack = "nextjs-ts"
+/// backend_stack = "node-ts"
+/// database = "postgres"
+///
+/// [presets.team-api]
+/// template = "acme-service"
+/// answers = { database = "postgres", docker = "true" }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Preset {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_type: Option<ProjectType>,
+    /// Stack ids, resolved when the preset is used so plugin stacks are only looked up then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frontend_stack: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend_stack: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<Database>,
+    /// Anything `init --template` accepts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Template prompt answers, like repeated `--set key=value`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub answers: BTreeMap<String, String>,
+}
+
+impl Preset {
+    /// The stack choices of an interactively configured project.
+    pub fn from_config(config: &ProjectConfig) -> Self {
+        Preset {
+            project_type: Some(config.project_type),
+            frontend_stack: config.frontend_stack.as_ref().map(|stack| stack.id().to_string()),
+            backend_stack: config.backend_stack.as_ref().map(|stack| stack.id().to_string()),
+            database: config.database,
+            ..Preset::default()
+        }
+    }
+
+    pub fn has_stack_choices(&self) -> bool {
+        self.project_type.is_some() || self.frontend_stack.is_some() || self.backend_stack.is_some() || self.database.is_some()
+    }
+
+    /// Fills in whatever `flags` leave open. A `--type` other than the preset's keeps only the
+    /// preset stacks that type has room for. Stack ids are looked up in `stacks`.
+    pub fn fill_in(&self, flags: StackChoices, stacks: &StackRegistry) -> Result<StackChoices, DevgeiniError> {
+        let keeps_frontend = flags.project_type.is_none_or(|project_type| project_type.has_frontend());
+        let keeps_backend = flags.project_type.is_none_or(|project_type| project_type.has_backend());
+
+        let frontend_stack = match flags.frontend_stack {
+            Some(stack) => Some(stack),
+            None => self.frontend_stack.as_deref().filter(|_| keeps_frontend).map(|id| stacks.frontend(id)).transpose()?,
+        };
+        let backend_stack = match flags.backend_stack {
+            Some(stack) => Some(stack),
+            None => self.backend_stack.as_deref().filter(|_| keeps_backend).map(|id| stacks.backend(id)).transpose()?,
+        };
+
+        Ok(StackChoices {
+            project_type: flags.project_type.or(self.project_type),
+            frontend_stack,
+            backend_stack,
+            database: flags.database.or(self.database.filter(|_| keeps_backend)),
+        })
+    }
+}
+
+/// The stack choices of an `init` run, from flags and a preset.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StackChoices {
+    pub project_type: Option<ProjectType>,
+    pub frontend_stack: Option<FrontendStack>,
+    pub backend_stack: Option<BackendStack>,
+    pub database: Option<Database>,
+}
+
+impl StackChoices {
+    pub fn is_empty(&self) -> bool {
+        *self == StackChoices::default()
+    }
+}
+
+pub fn user_config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config").join("devgeini").join("config.toml"))
+}
+
+impl UserConfig {
+    /// The saved config, or an empty one when the file does not exist yet.
+    pub fn load() -> Result<Self, DevgeiniError> {
+        match user_config_path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(UserConfig::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, DevgeiniError> {
+        if !path.exists() {
+            return Ok(UserConfig::default());
+        }
+
+        let contents = fs::read_to_string(path).map_err(|e| DevgeiniError::io(path, e))?;
+        let config: UserConfig = toml::from_str(&contents)
+            .map_err(|e| DevgeiniError::InvalidConfig(format!("could not parse {}: {}", path.display(), e)))?;
+
+        for (name, preset) in &config.presets {
+            if preset.template.is_some() && preset.has_stack_choices() {
+                return Err(DevgeiniError::InvalidConfig(format!(
+                    "preset '{}' in {} sets both a template and stacks, pick one",
+                    name,
+                    path.display()
+                )));
+            }
+            if preset.template.is_none() && !preset.answers.is_empty() {
+                return Err(DevgeiniError::InvalidConfig(format!(
+                    "preset '{}' in {} has template answers but no template",
+                    name,
+                    path.display()
+                )));
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Writes `preset` to the user config, replacing any preset with the same name.
+    pub fn save_preset(name: &str, preset: &Preset) -> Result<PathBuf, DevgeiniError> {
+        let path = user_config_path()
+            .ok_or_else(|| DevgeiniError::InvalidConfig("could not find the home directory".to_string()))?;
+        Self::save_preset_to(&path, name, preset)?;
+        Ok(path)
+    }
+
+    /// Only `[presets.<name>]` is rewritten, so comments and everything else in the file stay as they are.
+    pub fn save_preset_to(path: &Path, name: &str, preset: &Preset) -> Result<(), DevgeiniError> {
+        let is_new = !path.exists();
+        let contents = if is_new {
+            String::new()
+        } else {
+            fs::read_to_string(path).map_err(|e| DevgeiniError::io(path, e))?
+        };
+        let parse_error = |e: &dyn std::fmt::Display| DevgeiniError::InvalidConfig(format!("could not parse {}: {}", path.display(), e));
+        let mut document: DocumentMut = contents.parse().map_err(|e| parse_error(&e))?;
+
+        let presets = document
+            .entry("presets")
+            .or_insert_with(|| {
+                let mut presets = Table::new();
+                presets.set_implicit(true);
+                Item::Table(presets)
+            })
+            .as_table_mut()
+            .ok_or_else(|| parse_error(&"'presets' is not a table"))?;
+
+        let mut table = toml_edit::ser::to_document(preset)
+            .map_err(|e| DevgeiniError::InvalidConfig(format!("could not save preset '{}': {}", name, e)))?
+            .as_table()
+            .clone();
+        // A replaced preset keeps its place in the file and the comments above it
+        match presets.get(name).and_then(Item::as_table) {
+            Some(existing) => {
+                *table.decor_mut() = existing.decor().clone();
+                if let Some(position) = existing.position() {
+                    table.set_position(position);
+                }
+            }
+            None if is_new => table.decor_mut().set_prefix(format!("{}\n", CONFIG_HEADER)),
+            None => table.decor_mut().set_prefix("\n"),
+        }
+        presets.insert(name, Item::Table(table));
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| DevgeiniError::io(parent, e))?;
+        }
+        fs::write(path, document.to_string()).map_err(|e| DevgeiniError::io(path, e))
+    }
+
+    pub fn preset(&self, name: &str) -> Result<&Preset, String> {
+        self.presets.get(name).ok_or_else(|| {
+            if self.presets.is_empty() {
+                format!("there is no preset called '{}' (no presets are saved yet)", name)
+            } else {
+                let names: Vec<&str> = self.presets.keys().map(String::as_str).collect();
+                format!("there is no preset called '{}' (saved presets: {})", name, names.join(", "))
+            }
+        })
+    }
+}
+
+/// Preset names are used on the command line and as TOML keys, so keep them simple.
+pub fn validate_preset_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("preset name cannot be empty".to_string());
+    }
+    if let Some(c) = name.chars().find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))) {
+        return Err(format!("preset name cannot contain '{}', use letters, digits, '-' and '_'", c));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team_web() -> Preset {
+        Preset {
+            project_type: Some(ProjectType::FullStackWeb),
+            frontend_stack: Some("nextjs-ts".to_string()),
+            backend_stack: Some("node-ts".to_string()),
+            database: Some(Database::Postgres),
+            ..Preset::default()
+        }
+    }
+
+    #[test]
+    fn saved_presets_load_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("devgeini").join("config.toml");
+
+        UserConfig::save_preset_to(&path, "team-web", &team_web()).unwrap();
+        let api = Preset {
+            template: Some("acme-service".to_string()),
+            answers: BTreeMap::from([("database".to_string(), "mysql".to_string())]),
+            ..Preset::default()
+        };
+        UserConfig::save_preset_to(&path, "team-api", &api).unwrap();
+
+        let config = UserConfig::load_from(&path).unwrap();
+        let web = config.preset("team-web").unwrap();
+        assert_eq!(web.frontend_stack.as_deref(), Some("nextjs-ts"));
+        assert_eq!(web.database, Some(Database::Postgres));
+        let api = config.preset("team-api").unwrap();
+        assert_eq!(api.template.as_deref(), Some("acme-service"));
+        assert_eq!(api.answers["database"], "mysql");
+    }
+
+    #[test]
+    fn saving_a_preset_keeps_the_rest_of_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "# my presets\n\n# for the web team\n[presets.team-web]\nproject_type = \"frontend\" # old\nfrontend_stack = \"vue\"\n\n[presets.other]\ntemplate = \"./other\" # keep me\n",
+        )
+        .unwrap();
+
+        UserConfig::save_preset_to(&path, "team-web", &team_web()).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("# my presets\n\n# for the web team\n[presets.team-web]\n"), "{}", contents);
+        assert!(contents.contains("template = \"./other\" # keep me"), "{}", contents);
+        assert!(!contents.contains("# old"), "{}", contents);
+        assert!(contents.find("[presets.team-web]") < contents.find("[presets.other]"), "{}", contents);
+        let config = UserConfig::load_from(&path).unwrap();
+        assert_eq!(config.preset("team-web").unwrap().backend_stack.as_deref(), Some("node-ts"));
+    }
+
+    #[test]
+    fn missing_presets_name_the_saved_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        let empty = UserConfig::load_from(&path).unwrap();
+        assert!(empty.preset("team-web").unwrap_err().contains("no presets are saved yet"));
+
+        UserConfig::save_preset_to(&path, "team-web", &team_web()).unwrap();
+        let config = UserConfig::load_from(&path).unwrap();
+        assert!(config.preset("team-api").unwrap_err().contains("saved presets: team-web"));
+    }
+
+    #[test]
+    fn presets_mixing_templates_and_stacks_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[presets.mixed]\ntemplate = \"./service\"\nbackend_stack = \"go\"\n").unwrap();
+
+        assert!(matches!(UserConfig::load_from(&path), Err(DevgeiniError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn flags_override_the_preset() {
+        let stacks = StackRegistry::new();
+        let flags = StackChoices {
+            backend_stack: Some("go".parse().unwrap()),
+            database: Some(Database::Mysql),
+            ..StackChoices::default()
+        };
+
+        let choices = team_web().fill_in(flags, &stacks).unwrap();
+        assert_eq!(choices.project_type, Some(ProjectType::FullStackWeb));
+        assert_eq!(choices.frontend_stack.unwrap().id(), "nextjs-ts");
+        assert_eq!(choices.backend_stack.unwrap().id(), "go");
+        assert_eq!(choices.database, Some(Database::Mysql));
+    }
+
+    #[test]
+    fn another_project_type_drops_preset_stacks_it_has_no_room_for() {
+        let stacks = StackRegistry::new();
+        let backend = StackChoices {
+            project_type: Some(ProjectType::Backend),
+            ..StackChoices::default()
+        };
+        let choices = team_web().fill_in(backend, &stacks).unwrap();
+        assert_eq!(choices.frontend_stack, None);
+        assert_eq!(choices.backend_stack.unwrap().id(), "node-ts");
+        assert_eq!(choices.database, Some(Database::Postgres));
+
+        let cli = StackChoices {
+            project_type: Some(ProjectType::CliTool),
+            ..StackChoices::default()
+        };
+        let choices = team_web().fill_in(cli, &stacks).unwrap();
+        assert_eq!(choices, StackChoices { project_type: Some(ProjectType::CliTool), ..StackChoices::default() });
+    }
+
+    #[test]
+    fn unknown_preset_stacks_are_errors() {
+        let preset = Preset {
+            frontend_stack: Some("elm".to_string()),
+            ..Preset::default()
+        };
+
+        let result = preset.fill_in(StackChoices::default(), &StackRegistry::new());
+        assert!(matches!(result, Err(DevgeiniError::UnknownStack { .. })));
+    }
+}
@@ -0,0 +1,181 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config::structure::ProjectType;
+use crate::stacks::StackKind;
+
+/// Everything that can go wrong in devgeini.
+///
+/// Each variant exits with its own code (see `exit_code`) so scripts can tell a bad flag
+/// from a full disk without parsing the message.
+#[derive(Debug)]
+pub enum DevgeiniError {
+    /// A file or directory could not be read or written
+    Io { path: PathBuf, source: io::Error },
+    /// A built-in or user template has a syntax error or uses an unknown variable
+    TemplateRender { template: String, source: minijinja::Error },
+    /// A project template could not be fetched, or its manifest is broken
+    Template(String),
+    /// Flags, `project_config.toml`, a preset or a template answer describe something invalid
+    InvalidConfig(String),
+    /// A stack was combined with a project type that has no place for it
+    UnsupportedStack { stack: String, project_type: ProjectType },
+    /// No built-in or registered stack has this id
+    UnknownStack { kind: StackKind, id: String },
+    /// The target directory cannot be changed safely, e.g. it has uncommitted changes
+    Conflict(String),
+    /// GitHub could not be reached or answered with an error
+    Network(String),
+    /// A downloaded update did not contain a usable executable
+    UpdateVerification(String),
+    /// A `devgeini-plugin-*` executable failed or answered with something devgeini cannot use
+    Plugin { plugin: String, message: String },
+    /// A template's pre- or post-generation script failed to compile or run
+    Hook { script: PathBuf, message: String },
+    /// A prompt was cancelled
+    UserAborted,
+}
+
+impl DevgeiniError {
+    pub fn io(path: impl AsRef<Path>, source: io::Error) -> Self {
+        DevgeiniError::Io { path: path.as_ref().to_path_buf(), source }
+    }
+
+    /// The process exit code for this error. 2 stays "invalid configuration", as before.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            DevgeiniError::InvalidConfig(_) => 2,
+            DevgeiniError::UnsupportedStack { .. } | DevgeiniError::UnknownStack { .. } => 3,
+            DevgeiniError::Template(_) => 4,
+            DevgeiniError::TemplateRender { .. } => 5,
+            DevgeiniError::Io { .. } => 6,
+            DevgeiniError::Conflict(_) => 7,
+            DevgeiniError::Network(_) => 8,
+            DevgeiniError::UpdateVerification(_) => 9,
+            DevgeiniError::Plugin { .. } => 10,
+            DevgeiniError::Hook { .. } => 11,
+            // Same as being stopped with Ctrl-C
+            DevgeiniError::UserAborted => 130,
+        }
+    }
+}
+
+impl fmt::Display for DevgeiniError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DevgeiniError::Io { path, source } => {
+                write!(f, "{}: {}", path.display(), source)?;
+                match source.kind() {
+                    io::ErrorKind::PermissionDenied => write!(f, " (check the permissions of this path)"),
+                    io::ErrorKind::AlreadyExists => write!(f, " (remove it or pick another --output-dir)"),
+                    _ => Ok(()),
+                }
+            }
+            DevgeiniError::TemplateRender { template, source } => {
+                write!(f, "could not render template '{}'", template)?;
+                if let Some(line) = source.line() {
+                    write!(f, " at line {}", line)?;
+                }
+                write!(f, ": {}", source.kind())?;
+                if let Some(detail) = source.detail() {
+                    write!(f, ": {}", detail)?;
+                }
+                Ok(())
+            }
+            DevgeiniError::Template(message) => write!(f, "{}", message),
+            DevgeiniError::InvalidConfig(message) => write!(f, "{}", message),
+            DevgeiniError::UnsupportedStack { stack, project_type } => write!(
+                f,
+                "stack '{}' cannot be used with a '{}' project; run 'devgeini list' to see which project types each stack supports",
+                stack, project_type
+            ),
+            DevgeiniError::UnknownStack { kind, id } => write!(
+                f,
+                "unknown {} stack '{}'; run 'devgeini list' to see the available stacks",
+                kind.dir(),
+                id
+            ),
+            DevgeiniError::Conflict(message) => write!(f, "{}", message),
+            DevgeiniError::Network(message) => write!(f, "{} (check your internet connection and try again)", message),
+            DevgeiniError::UpdateVerification(message) => write!(
+                f,
+                "{}; your current installation was left unchanged",
+                message
+            ),
+            DevgeiniError::Plugin { plugin, message } => write!(f, "plugin '{}' {}", plugin, message),
+            DevgeiniError::Hook { script, message } => write!(f, "hook {} failed: {}", script.display(), message),
+            DevgeiniError::UserAborted => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for DevgeiniError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DevgeiniError::Io { source, .. } => Some(source),
+            DevgeiniError::TemplateRender { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+// minijinja knows which template failed, inline strings (file names, defaults) have no name
+impl From<minijinja::Error> for DevgeiniError {
+    fn from(source: minijinja::Error) -> Self {
+        DevgeiniError::TemplateRender {
+            template: source.name().unwrap_or("<inline>").to_string(),
+            source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_kind_of_failure_has_its_own_exit_code() {
+        let errors = [
+            DevgeiniError::InvalidConfig(String::new()),
+            DevgeiniError::UnsupportedStack { stack: "react".to_string(), project_type: ProjectType::Backend },
+            DevgeiniError::Template(String::new()),
+            DevgeiniError::TemplateRender {
+                template: "README.md.j2".to_string(),
+                source: minijinja::Error::new(minijinja::ErrorKind::UndefinedError, "name"),
+            },
+            DevgeiniError::io("README.md", io::Error::from(io::ErrorKind::NotFound)),
+            DevgeiniError::Conflict(String::new()),
+            DevgeiniError::Network(String::new()),
+            DevgeiniError::UpdateVerification(String::new()),
+            DevgeiniError::Plugin { plugin: "elm".to_string(), message: String::new() },
+            DevgeiniError::Hook { script: PathBuf::from("hooks/post.rhai"), message: String::new() },
+            DevgeiniError::UserAborted,
+        ];
+
+        let codes: Vec<i32> = errors.iter().map(DevgeiniError::exit_code).collect();
+        assert_eq!(codes, [2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 130]);
+        let unknown = DevgeiniError::UnknownStack { kind: StackKind::Backend, id: "cobol".to_string() };
+        assert_eq!(unknown.exit_code(), 3);
+    }
+
+    #[test]
+    fn messages_say_what_to_do_next() {
+        let unknown = DevgeiniError::UnknownStack { kind: StackKind::Frontend, id: "elm".to_string() };
+        assert_eq!(
+            unknown.to_string(),
+            "unknown frontend stack 'elm'; run 'devgeini list' to see the available stacks"
+        );
+
+        let denied = DevgeiniError::io("/etc/shop", io::Error::from(io::ErrorKind::PermissionDenied));
+        assert!(denied.to_string().starts_with("/etc/shop: "), "{}", denied);
+        assert!(denied.to_string().ends_with("(check the permissions of this path)"), "{}", denied);
+        assert!(std::error::Error::source(&denied).is_some());
+
+        let render = DevgeiniError::TemplateRender {
+            template: "common/README.md.j2".to_string(),
+            source: minijinja::Error::new(minijinja::ErrorKind::UndefinedError, "name"),
+        };
+        assert!(render.to_string().starts_with("could not render template 'common/README.md.j2'"), "{}", render);
+    }
+}
@@ -0,0 +1,66 @@
+use serde::Serialize;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config::structure::ProjectConfig;
+use crate::utils::createproject::write_project;
+use crate::utils::writer::ProjectWriter;
+use crate::Error;
+
+/// What `generate` wrote, with paths relative to the project root in the order they were written.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GenerationReport {
+    pub directories: Vec<PathBuf>,
+    pub files: Vec<PathBuf>,
+    /// Total size of all files
+    pub bytes: u64,
+    /// Lines the user's hooks printed, for the caller to show
+    pub hook_output: Vec<String>,
+}
+
+/// Validates `config` and writes the complete project into `writer`.
+///
+/// Besides the built-in generators this runs code from the user's machine:
+///
+/// - the Rhai hooks in `~/.config/devgeini/templates/hooks`, which may change the variables
+///   and the generated files (sandboxed, they cannot reach the file system or run commands)
+/// - the `devgeini-plugin-*` executable providing a stack, when `config` selects a stack that
+///   was resolved with a registry from `StackRegistry::with_plugins`
+///
+/// `writer.finish()` is left to the caller, so more files can be added to an archive
+/// before it is closed.
+pub fn generate(config: &ProjectConfig, writer: &mut dyn ProjectWriter) -> Result<GenerationReport, Error> {
+    config.validate()?;
+
+    let mut recorder = RecordingWriter {
+        inner: writer,
+        report: GenerationReport::default(),
+    };
+    let hook_output = write_project(config, &mut recorder)?;
+
+    Ok(GenerationReport {
+        hook_output,
+        ..recorder.report
+    })
+}
+
+// Passes everything through to `inner` while noting it down for the report
+struct RecordingWriter<'a> {
+    inner: &'a mut dyn ProjectWriter,
+    report: GenerationReport,
+}
+
+impl ProjectWriter for RecordingWriter<'_> {
+    fn add_dir(&mut self, path: &Path) -> io::Result<()> {
+        self.inner.add_dir(path)?;
+        self.report.directories.push(path.to_path_buf());
+        Ok(())
+    }
+
+    fn add_file(&mut self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        self.inner.add_file(path, contents)?;
+        self.report.files.push(path.to_path_buf());
+        self.report.bytes += contents.len() as u64;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+//! Project scaffolding behind the `devgeini` CLI.
+//!
+//! ```
+//! use devgeini::{generate, MemoryWriter, ProjectConfig, ProjectType};
+//! use std::path::Path;
+//!
+//! let mut config = ProjectConfig::new("my-app".to_string(), ProjectType::Frontend);
+//! config.frontend_stack = Some("react-ts".parse()?);
+//!
+//! // `DiskWriter::new(&config.root)` writes the same files to disk
+//! let mut files = MemoryWriter::new();
+//! let report = generate(&config, &mut files)?;
+//! assert!(report.files.iter().any(|path| path == Path::new("src/App.tsx")));
+//! assert_eq!(files.files().count(), report.files.len());
+//! # Ok::<(), devgeini::Error>(())
+//! ```
+//!
+//! Like the CLI, [`generate`] picks up the user's hooks, see its documentation. Plugin stacks are
+//! only available after loading them with [`StackRegistry::with_plugins`].
+//! The prompts, conflict handling and self-update of the CLI are not part of the library, and
+//! it prints nothing: hook output, skipped plugins and other warnings are returned to the caller,
+//! e.g. in [`GenerationReport::hook_output`] and [`StackRegistry::warnings`].
+
+pub mod config;
+pub mod error;
+pub mod stacks;
+mod generate;
+mod templates;
+mod utils;
+
+pub use config::structure::{BackendStack, Database, FrontendStack, ProjectConfig, ProjectType};
+pub use error::DevgeiniError;
+pub use generate::{generate, GenerationReport};
+pub use stacks::registry::StackRegistry;
+pub use stacks::{StackGenerator, StackKind};
+pub use utils::writer::{ArchiveWriter, DiskWriter, MemoryWriter, ProjectWriter};
+
+pub type Error = DevgeiniError;
+
+/// Project templates from git or a local directory, and the index `devgeini list` shows.
+pub mod template {
+    pub use crate::templates::external::{
+        installed_dir, is_remote, AskPrompt, ExternalTemplate, FileRules, Prompt, TemplateFormat, TemplateManifest,
+        TemplateRecord, MANIFEST_FILE, RECORD_FILE,
+    };
+    pub use crate::templates::extract::extract_template;
+    pub use crate::templates::registry::{template_index, EntryKind, TemplateEntry, TemplateIndex};
+}
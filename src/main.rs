@@ -1,51 +1,27 @@
 use clap::{Arg, Command};
-use dialoguer::{theme::ColorfulTheme, Select, Input, Confirm};
-use serde_json::json;
-use std::fs;
-use std::path::Path;
-use std::io::{self, Write};
-use std::process::{Command as ShellCommand, Stdio};
-use std::collections::HashMap;
-use serde::{Deserialize, Serialize};
-use reqwest;
-use tokio;
-
-mod config;
-//import all emnum and structure from config module
-use config::structure::{ProjectConfig, FrontendStack, BackendStack,ProjectType};
-mod utils;
-use utils::createproject::{get_project_name, get_project_config_interactive, select_project_type, select_frontend_stack, select_backend_stack, create_project};
-mod templates;
-
-use crate::templates::frontend::{
-    create_react_project, create_react_ts_project, create_vue_project, create_vue_ts_project,
-    create_nextjs_project, create_nextjs_ts_project, create_svelte_project, create_svelte_ts_project,
-    create_vanilla_project, create_vanilla_ts_project, create_angular_project,
-};
-use crate::templates::backend::{
-    create_nodejs_backend, create_nodejs_ts_backend, create_python_backend, create_rust_backend,
-};
-
-// GitHub API structures
-#[derive(Debug, Deserialize)]
-struct GitHubRelease {
-    tag_name: String,
-    name: String,
-    body: String,
-    assets: Vec<GitHubAsset>,
-    prerelease: bool,
-}
+use dialoguer::{theme::ColorfulTheme, Select};
+use std::collections::BTreeMap;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Deserialize)]
-struct GitHubAsset {
-    name: String,
-    browser_download_url: String,
-    size: u64,
-}
-
-const GITHUB_REPO: &str = "abhix2112/devgeini"; // Replace with your actual GitHub repo
-const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+mod cli;
 
+use devgeini::{config, stacks, template, DevgeiniError};
+//import all emnum and structure from config module
+use config::structure::{ProjectConfig, FrontendStack, BackendStack, Database, ProjectType};
+use config::naming::validate_project_name;
+use config::user::{Preset, StackChoices, UserConfig};
+use cli::conflicts::OverwriteMode;
+use cli::stacks::{plugin_registry, with_stacks, StackParser};
+use cli::staging::{install_interrupt_handler, remove_on_interrupt};
+use cli::create::{
+    create_project, preview_project, archive_project, create_project_with, preview_project_with, archive_project_with,
+};
+use cli::update::{check_for_updates, check_for_updates_silent, handle_update};
+use cli::wizard::{ask_template_prompt, prompt_error, get_project_name, get_project_config_interactive, get_project_config_from_flags};
+use devgeini::ProjectWriter;
+use template::{extract_template, installed_dir, is_remote, template_index, AskPrompt, EntryKind, ExternalTemplate, TemplateEntry};
+use stacks::{StackGenerator, StackKind};
 
 async fn show_welcome_menu() {
     println!("\n🚀 Welcome to Devgeini - Your Dev CLI Companion!");
@@ -69,22 +45,25 @@ async fn show_welcome_menu() {
         .default(0)
         .items(&options)
         .interact()
-        .unwrap();
+        .unwrap_or_else(|e| exit_with_error("Could not show the menu", prompt_error(e)));
 
     match selection {
         0 => {
             // Create new project
             println!("\n🛠️  Starting project creation...\n");
-            let project_name = get_project_name();
-            let config = get_project_config_interactive(project_name);
+            let config = match get_project_name().and_then(get_project_config_interactive) {
+                Ok(config) => config,
+                Err(e) => exit_with_error("Invalid project configuration", e),
+            };
             
-            if let Err(e) = create_project(&config) {
-                eprintln!("❌ Error creating project: {}", e);
-                std::process::exit(1);
+            if let Err(e) = create_project(&config, OverwriteMode::Ask) {
+                exit_with_error("Error creating project", e);
             }
 
             println!("🎉 Project '{}' created successfully!", config.name);
-            println!("📁 Navigate to your project: cd {}", config.name);
+            if config.root != Path::new(".") {
+                println!("📁 Navigate to your project: cd {}", config.root.display());
+            }
             show_next_steps(&config);
         }
         1 => {
@@ -116,53 +95,346 @@ async fn handle_init_command(matches: &clap::ArgMatches) {
         let _ = check_for_updates_silent().await;
     });
 
-    let project_name = if let Some(name) = matches.get_one::<String>("name") {
-        name.clone()
-    } else {
-        get_project_name()
-    };
+    let preset = matches.get_one::<String>("preset").map(|name| load_preset(name));
+    let has_stack_flags = matches.contains_id("type")
+        || matches.contains_id("frontend")
+        || matches.contains_id("backend")
+        || matches.contains_id("database");
+
+    let template = matches
+        .get_one::<String>("template")
+        .or_else(|| preset.as_ref().and_then(|preset| preset.template.as_ref()));
+    if let Some(source) = template {
+        if has_stack_flags {
+            exit_with_error(
+                "Invalid project configuration",
+                DevgeiniError::InvalidConfig(
+                    "the preset uses a template, so --type, --frontend, --backend and --database do not apply".to_string(),
+                ),
+            );
+        }
+        let answers = preset.as_ref().map(|preset| preset.answers.clone()).unwrap_or_default();
+        if let Err((context, e)) = init_from_template(matches, source, answers) {
+            exit_with_error(context, e);
+        }
+        return;
+    }
+    if matches.contains_id("set") {
+        exit_with_error(
+            "Invalid project configuration",
+            DevgeiniError::InvalidConfig(
+                "--set answers template prompts and needs --template or a preset with a template".to_string(),
+            ),
+        );
+    }
 
-    let config = if matches.get_flag("interactive") || matches.get_one::<String>("name").is_none() {
-        get_project_config_interactive(project_name)
-    } else {
-        // Quick mode - just get project type
-        let project_type = select_project_type();
-        let mut config = ProjectConfig {
-            name: project_name,
-            project_type: project_type.clone(),
-            frontend_stack: None,
-            backend_stack: None,
-        };
-        
-        match project_type {
-            ProjectType::FullStackWeb => {
-                config.frontend_stack = Some(select_frontend_stack());
-                config.backend_stack = Some(select_backend_stack());
-            }
-            ProjectType::Frontend => {
-                config.frontend_stack = Some(select_frontend_stack());
+    let mut config = if let Some(config_path) = matches.get_one::<PathBuf>("config") {
+        // Regenerate from a saved project_config.toml, --name still wins if given
+        match with_stacks(|stacks| ProjectConfig::load(config_path, stacks)) {
+            Ok(mut config) => {
+                if let Some(name) = matches.get_one::<String>("name") {
+                    config.set_name(name.clone());
+                }
+                config
             }
-            ProjectType::Backend => {
-                config.backend_stack = Some(select_backend_stack());
+            Err(e) => {
+                exit_with_error("Invalid project configuration", e);
             }
-            _ => {}
         }
-        
-        config
+    } else {
+        get_config_from_matches(matches, preset.as_ref())
     };
 
-    if let Err(e) = create_project(&config) {
-        eprintln!("❌ Error creating project: {}", e);
-        std::process::exit(1);
+    // The package name stays config.name, only the location changes
+    if let Some(output_dir) = matches.get_one::<PathBuf>("output-dir") {
+        config.root = output_dir.clone();
+    }
+
+    if matches.get_flag("dry-run") {
+        if let Err(e) = preview_project(&config, matches.get_flag("show-contents")) {
+            exit_with_error("Error previewing project", e);
+        }
+        return;
+    }
+
+    if let Some(archive_path) = matches.get_one::<PathBuf>("archive") {
+        if let Err(e) = archive_project(&config, archive_path) {
+            exit_with_error("Error creating archive", e);
+        }
+        return;
+    }
+
+    if let Err(e) = create_project(&config, overwrite_mode(matches)) {
+        exit_with_error("Error creating project", e);
     }
 
     println!("🎉 Project '{}' created successfully!", config.name);
-    println!("📁 Navigate to your project: cd {}", config.name);
+    if config.root != Path::new(".") {
+        println!("📁 Navigate to your project: cd {}", config.root.display());
+    }
     
     // Enhanced stack-specific instructions
     show_next_steps(&config);
 }
 
+// --yes keeps its old meaning of "replace the existing directory", now with a backup
+fn overwrite_mode(matches: &clap::ArgMatches) -> OverwriteMode {
+    if matches.get_flag("merge") {
+        OverwriteMode::Merge
+    } else if matches.get_flag("force") || matches.get_flag("yes") {
+        OverwriteMode::Force
+    } else {
+        OverwriteMode::Ask
+    }
+}
+
+// Exits with the same code as an invalid project configuration when the preset is missing
+fn load_preset(name: &str) -> Preset {
+    let preset = UserConfig::load()
+        .and_then(|user_config| user_config.preset(name).cloned().map_err(DevgeiniError::InvalidConfig));
+    match preset {
+        Ok(preset) => {
+            println!("📌 Using preset '{}'", name);
+            preset
+        }
+        Err(e) => {
+            exit_with_error("Invalid preset", e);
+        }
+    }
+}
+
+// `answers` come from a preset, --set values win over them. Errors are returned with their
+// context rather than exiting here, so a cloned template is removed before the process ends.
+fn init_from_template(
+    matches: &clap::ArgMatches,
+    source: &str,
+    mut answers: BTreeMap<String, String>,
+) -> Result<(), (&'static str, DevgeiniError)> {
+    let name = match matches.get_one::<String>("name") {
+        Some(name) => name.clone(),
+        None => get_project_name().map_err(|e| ("Invalid project name", e))?,
+    };
+    let root = match matches.get_one::<PathBuf>("output-dir") {
+        Some(output_dir) => output_dir.clone(),
+        None => PathBuf::from(&name),
+    };
+    answers.extend(matches.get_many::<(String, String)>("set").into_iter().flatten().cloned());
+
+    if is_remote(source) {
+        println!("📥 Fetching template from {}...", source);
+    }
+    let template = ExternalTemplate::fetch(source).map_err(|e| ("Error loading template", e))?;
+    let _cleanup = template.clone_dir().map(remove_on_interrupt);
+    print_warnings(&template.warnings);
+    println!("🧩 Using template '{}'", template.manifest.name);
+    if !template.manifest.description.is_empty() {
+        println!("   {}", template.manifest.description);
+    }
+
+    // Without a terminal every prompt that --set did not answer takes its default
+    let ask_prompt: Option<AskPrompt> = if io::stdin().is_terminal() { Some(&ask_template_prompt) } else { None };
+    let answers = template
+        .ask(&answers, &name, ask_prompt)
+        .map_err(|e| ("Invalid template answers", e))?;
+    let generate = |writer: &mut dyn ProjectWriter| template.render(&name, &answers, writer);
+
+    if matches.get_flag("dry-run") {
+        return preview_project_with(&root, matches.get_flag("show-contents"), &generate)
+            .map_err(|e| ("Error previewing project", e));
+    }
+
+    if let Some(archive_path) = matches.get_one::<PathBuf>("archive") {
+        return archive_project_with(archive_path, &name, &generate).map_err(|e| ("Error creating archive", e));
+    }
+
+    create_project_with(&root, overwrite_mode(matches), &generate).map_err(|e| ("Error creating project", e))?;
+
+    println!("🎉 Project '{}' created successfully!", name);
+    if root != Path::new(".") {
+        println!("📁 Navigate to your project: cd {}", root.display());
+    }
+    Ok(())
+}
+
+// Prints the error and exits with its code, so scripts can tell failures apart
+fn exit_with_error(context: &str, error: DevgeiniError) -> ! {
+    eprintln!("❌ {}: {}", context, error);
+    std::process::exit(error.exit_code());
+}
+
+fn handle_template_command(matches: &clap::ArgMatches) {
+    if let Some(("new", sub_matches)) = matches.subcommand() {
+        handle_template_new(sub_matches);
+    }
+}
+
+fn handle_template_new(matches: &clap::ArgMatches) {
+    let source = matches.get_one::<PathBuf>("source").unwrap();
+    let source = match std::fs::canonicalize(source) {
+        Ok(source) if source.is_dir() => source,
+        _ => exit_with_error(
+            "Invalid template source",
+            DevgeiniError::InvalidConfig(format!("'{}' is not a project directory", source.display())),
+        ),
+    };
+
+    // A devgeini project knows its own name, anything else is named after its directory
+    let name = match matches.get_one::<String>("name") {
+        Some(name) => name.clone(),
+        None => with_stacks(|stacks| ProjectConfig::load(&source.join("project_config.toml"), stacks))
+            .map(|config| config.name)
+            .unwrap_or_else(|_| source.file_name().unwrap_or_default().to_string_lossy().into_owned()),
+    };
+    let output = match matches.get_one::<PathBuf>("output") {
+        Some(output) => output.clone(),
+        None => source.with_file_name(format!("{}-template", name)),
+    };
+
+    let output_parent = output.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    if std::fs::canonicalize(output_parent).is_ok_and(|parent| parent.starts_with(&source)) {
+        exit_with_error(
+            "Invalid template output",
+            DevgeiniError::InvalidConfig(
+                "the template has to be written outside of the project it is made from".to_string(),
+            ),
+        );
+    }
+
+    println!("🧩 Turning '{}' into a template, replacing the name '{}'", source.display(), name);
+    let generate = |writer: &mut dyn ProjectWriter| extract_template(&source, &name, writer).map(|_| Vec::new());
+
+    if matches.get_flag("dry-run") {
+        if let Err(e) = preview_project_with(&output, matches.get_flag("show-contents"), &generate) {
+            exit_with_error("Error previewing template", e);
+        }
+        return;
+    }
+
+    if let Err(e) = create_project_with(&output, overwrite_mode(matches), &generate) {
+        exit_with_error("Error creating template", e);
+    }
+
+    println!("🎉 Template written to {}", output.display());
+    println!("💡 Try it: devgeini init --name <name> --template {}", output.display());
+}
+
+fn handle_list_command(matches: &clap::ArgMatches) {
+    let index = template_index(plugin_registry());
+    print_warnings(&index.warnings);
+    let entries = index.entries;
+    let json = matches.get_flag("json");
+    let mut out = io::stdout().lock();
+
+    let result = write_template_entries(&mut out, &entries, json).and_then(|_| {
+        if !json && !entries.iter().any(|entry| entry.kind == EntryKind::Template) {
+            if let Some(dir) = installed_dir() {
+                writeln!(out, "\n💡 Put project templates in {} to use them by name", dir.display())?;
+            }
+        }
+        Ok(())
+    });
+    finish_output(result);
+}
+
+fn handle_search_command(matches: &clap::ArgMatches) {
+    let term = matches.get_one::<String>("term").unwrap();
+    let index = template_index(plugin_registry());
+    print_warnings(&index.warnings);
+    let entries: Vec<TemplateEntry> = index.entries.into_iter().filter(|entry| entry.matches(term)).collect();
+    let mut out = io::stdout().lock();
+
+    if entries.is_empty() && !matches.get_flag("json") {
+        finish_output(writeln!(out, "🔍 Nothing matches '{}'. Run 'devgeini list' to see everything.", term));
+        return;
+    }
+    finish_output(write_template_entries(&mut out, &entries, matches.get_flag("json")));
+}
+
+// On stderr, so `list --json` and the like stay parseable
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+}
+
+// Output piped into `head` and the like may be cut off early, which is not an error
+fn finish_output(result: io::Result<()>) {
+    match result {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+        Err(e) => exit_with_error("Error writing output", DevgeiniError::io("<stdout>", e)),
+        Ok(()) => {}
+    }
+}
+
+fn write_template_entries(out: &mut impl Write, entries: &[TemplateEntry], json: bool) -> io::Result<()> {
+    if json {
+        serde_json::to_writer_pretty(&mut *out, entries)?;
+        return writeln!(out);
+    }
+
+    let id_width = entries.iter().map(|entry| entry.id.len()).max().unwrap_or_default();
+    let language_width = entries.iter().map(|entry| entry.language.len()).max().unwrap_or_default().max(1);
+    let mut heading = None;
+
+    for entry in entries {
+        if heading != Some(entry.kind) {
+            writeln!(out, "\n{}", entry.kind.heading())?;
+            heading = Some(entry.kind);
+        }
+
+        let language = if entry.language.is_empty() { "-" } else { &entry.language };
+        let project_types: Vec<String> = entry.project_types.iter().map(|t| t.to_string()).collect();
+        let description = if entry.description.is_empty() { &entry.name } else { &entry.description };
+        write!(out, "  {:<id_width$}  {:<language_width$}  {}", entry.id, language, description)?;
+        if !project_types.is_empty() && entry.kind != EntryKind::ProjectType {
+            write!(out, " ({})", project_types.join(", "))?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn get_config_from_matches(matches: &clap::ArgMatches, preset: Option<&Preset>) -> ProjectConfig {
+    let project_name = if let Some(name) = matches.get_one::<String>("name") {
+        name.clone()
+    } else {
+        get_project_name().unwrap_or_else(|e| exit_with_error("Invalid project name", e))
+    };
+
+    let flags = StackChoices {
+        project_type: matches.get_one::<ProjectType>("type").copied(),
+        frontend_stack: matches.get_one::<FrontendStack>("frontend").cloned(),
+        backend_stack: matches.get_one::<BackendStack>("backend").cloned(),
+        database: matches.get_one::<Database>("database").copied(),
+    };
+    // Flags override the preset's choices, whose stack ids are looked up like the flags
+    let choices = match preset {
+        Some(preset) => with_stacks(|stacks| preset.fill_in(flags.clone(), stacks))
+            .unwrap_or_else(|e| exit_with_error("Invalid preset", e)),
+        None => flags,
+    };
+
+    // Without any stack choices the wizard runs, which also asks for a database and offers a preset
+    let config = if choices.is_empty() {
+        get_project_config_interactive(project_name)
+    } else {
+        // Flag mode - only prompt for whatever was not passed on the command line
+        get_project_config_from_flags(
+            project_name,
+            choices.project_type,
+            choices.frontend_stack,
+            choices.backend_stack,
+            choices.database,
+        )
+    };
+    match config {
+        Ok(config) => config,
+        Err(e) => {
+            exit_with_error("Invalid project configuration", e);
+        }
+    }
+}
+
 fn show_help_menu() {
     println!("\n📖 Devgeini Help");
     println!("================");
@@ -171,6 +443,19 @@ fn show_help_menu() {
     println!("🎯 devgeini init                    - Start creating a new project");
     println!("🎯 devgeini init --name <name>      - Create project with specific name");
     println!("🎯 devgeini init --interactive      - Run in full interactive mode");
+    println!("🎯 devgeini init --name <name> --type <type> [--frontend <stack>] [--backend <stack>] [--database <db>] [--yes]");
+    println!("                                    - Create a project without any prompts");
+    println!("🎯 devgeini init --config <file>    - Regenerate a project from its project_config.toml");
+    println!("🎯 devgeini init --template <src>   - Generate from a git or local project template (answer prompts with --set k=v)");
+    println!("🎯 devgeini init --preset <name>    - Reuse choices saved in ~/.config/devgeini/config.toml");
+    println!("🎯 devgeini init --output-dir <dir> - Generate somewhere other than ./<name> ('.' for here)");
+    println!("🎯 devgeini init --force            - Back up an existing project directory and replace it");
+    println!("🎯 devgeini init --merge            - Only add files missing from an existing project directory");
+    println!("🎯 devgeini init --dry-run          - Preview the generated file tree (add --show-contents for files)");
+    println!("🎯 devgeini init --archive <file>   - Write the project into a .zip or .tar.gz instead");
+    println!("🧩 devgeini template new [dir]      - Turn an existing project into a reusable template");
+    println!("📋 devgeini list [--json]           - List project types, stacks and installed templates");
+    println!("🔍 devgeini search <term>           - Search that list by name, language or description");
     println!();
     println!("🔄 devgeini --update               - Update to latest version");
     println!("🔍 devgeini --check-update         - Check if updates are available");
@@ -202,6 +487,7 @@ async fn main() {
                         .short('n')
                         .long("name")
                         .value_name("PROJECT_NAME")
+                        .value_parser(|name: &str| validate_project_name(name).map(|_| name.to_string()))
                         .help("Sets the project name")
                 )
                 .arg(
@@ -211,6 +497,214 @@ async fn main() {
                         .action(clap::ArgAction::SetTrue)
                         .help("Run in interactive mode")
                 )
+                .arg(
+                    Arg::new("type")
+                        .short('t')
+                        .long("type")
+                        .value_name("PROJECT_TYPE")
+                        .value_parser(clap::value_parser!(ProjectType))
+                        .help("Sets the project type")
+                )
+                .arg(
+                    Arg::new("frontend")
+                        .short('f')
+                        .long("frontend")
+                        .value_name("STACK")
+                        .value_parser(StackParser::frontend())
+                        .help("Sets the frontend stack (fullstack and frontend projects, see 'devgeini list')")
+                )
+                .arg(
+                    Arg::new("backend")
+                        .short('b')
+                        .long("backend")
+                        .value_name("STACK")
+                        .value_parser(StackParser::backend())
+                        .help("Sets the backend stack (fullstack and backend projects, see 'devgeini list')")
+                )
+                .arg(
+                    Arg::new("database")
+                        .long("database")
+                        .value_name("DATABASE")
+                        .value_parser(clap::value_parser!(Database))
+                        .help("Sets DATABASE_URL in .env.example and the database service of a fullstack project's docker-compose.yml (fullstack and backend projects)")
+                )
+                .arg(
+                    Arg::new("config")
+                        .short('c')
+                        .long("config")
+                        .value_name("FILE")
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .conflicts_with_all(["interactive", "type", "frontend", "backend", "database"])
+                        .help("Regenerate a project from a project_config.toml")
+                )
+                .arg(
+                    Arg::new("template")
+                        .long("template")
+                        .value_name("GIT_URL|PATH")
+                        .conflicts_with_all(["interactive", "type", "frontend", "backend", "database", "config"])
+                        .help("Generate from a project template: a git URL, a file:// URL or a local directory")
+                )
+                .arg(
+                    Arg::new("set")
+                        .long("set")
+                        .value_name("KEY=VALUE")
+                        .action(clap::ArgAction::Append)
+                        .value_parser(|pair: &str| {
+                            pair.split_once('=')
+                                .map(|(key, value)| (key.to_string(), value.to_string()))
+                                .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", pair))
+                        })
+                        .help("Answer a template prompt without asking (repeatable)")
+                )
+                .arg(
+                    Arg::new("preset")
+                        .short('p')
+                        .long("preset")
+                        .value_name("NAME")
+                        .conflicts_with_all(["interactive", "config", "template"])
+                        .help("Use choices saved in ~/.config/devgeini/config.toml (--type/--frontend/--backend override them)")
+                )
+                .arg(
+                    Arg::new("output-dir")
+                        .short('o')
+                        .long("output-dir")
+                        .visible_alias("path")
+                        .value_name("DIR")
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .help("Directory to generate into, '.' for the current directory (defaults to the project name)")
+                )
+                .arg(
+                    Arg::new("yes")
+                        .short('y')
+                        .long("yes")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Automatically confirm overwriting an existing project directory (same as --force)")
+                )
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .action(clap::ArgAction::SetTrue)
+                        .conflicts_with("merge")
+                        .help("Replace an existing project directory after moving it to a timestamped backup")
+                )
+                .arg(
+                    Arg::new("merge")
+                        .long("merge")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Only write files that do not exist in the project directory yet")
+                )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Print the file tree that would be generated without writing anything")
+                )
+                .arg(
+                    Arg::new("show-contents")
+                        .long("show-contents")
+                        .action(clap::ArgAction::SetTrue)
+                        .requires("dry-run")
+                        .help("With --dry-run, also print the contents of every file")
+                )
+                .arg(
+                    Arg::new("archive")
+                        .long("archive")
+                        .value_name("FILE")
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .conflicts_with_all(["dry-run", "force", "merge"])
+                        .help("Write the project into a .zip or .tar.gz file instead of a directory")
+                )
+        )
+        .subcommand(
+            Command::new("template")
+                .about("Create and manage project templates")
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("new")
+                        .about("Turn an existing project into a template that init --template can use")
+                        .arg(
+                            Arg::new("source")
+                                .value_name("PROJECT_DIR")
+                                .default_value(".")
+                                .value_parser(clap::value_parser!(PathBuf))
+                                .help("The project to turn into a template")
+                        )
+                        .arg(
+                            Arg::new("name")
+                                .short('n')
+                                .long("name")
+                                .value_name("PROJECT_NAME")
+                                .help("The project name to replace with placeholders (defaults to the directory name)")
+                        )
+                        .arg(
+                            Arg::new("output")
+                                .short('o')
+                                .long("output")
+                                .value_name("DIR")
+                                .value_parser(clap::value_parser!(PathBuf))
+                                .help("Where to write the template (defaults to <name>-template next to the project)")
+                        )
+                        .arg(
+                            Arg::new("yes")
+                                .short('y')
+                                .long("yes")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Automatically confirm overwriting an existing template directory (same as --force)")
+                        )
+                        .arg(
+                            Arg::new("force")
+                                .long("force")
+                                .action(clap::ArgAction::SetTrue)
+                                .conflicts_with("merge")
+                                .help("Replace an existing template directory after moving it to a timestamped backup")
+                        )
+                        .arg(
+                            Arg::new("merge")
+                                .long("merge")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Only write files that do not exist in the template directory yet")
+                        )
+                        .arg(
+                            Arg::new("dry-run")
+                                .long("dry-run")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Print the template's file tree without writing anything")
+                        )
+                        .arg(
+                            Arg::new("show-contents")
+                                .long("show-contents")
+                                .action(clap::ArgAction::SetTrue)
+                                .requires("dry-run")
+                                .help("With --dry-run, also print the contents of every file")
+                        )
+                )
+        )
+        .subcommand(
+            Command::new("list")
+                .about("List the project types, stacks and installed templates devgeini can generate")
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Print the list as JSON")
+                )
+        )
+        .subcommand(
+            Command::new("search")
+                .about("Search project types, stacks and installed templates")
+                .arg(
+                    Arg::new("term")
+                        .value_name("TERM")
+                        .required(true)
+                        .help("Matched against ids, names, descriptions, languages and project types")
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Print the matches as JSON")
+                )
         )
         .arg(
             Arg::new("update")
@@ -227,19 +721,20 @@ async fn main() {
         )
         .get_matches();
 
+    // Staging directories and cloned templates register themselves to be removed on Ctrl-C
+    install_interrupt_handler();
+
     // Handle update commands first
     if matches.get_flag("update") {
         if let Err(e) = handle_update().await {
-            eprintln!("❌ Update failed: {}", e);
-            std::process::exit(1);
+            exit_with_error("Update failed", e);
         }
         return;
     }
 
     if matches.get_flag("check-update") {
         if let Err(e) = check_for_updates().await {
-            eprintln!("❌ Failed to check for updates: {}", e);
-            std::process::exit(1);
+            exit_with_error("Failed to check for updates", e);
         }
         return;
     }
@@ -249,262 +744,39 @@ async fn main() {
         Some(("init", sub_matches)) => {
             handle_init_command(sub_matches).await;
         }
-        _ => {
-            // No subcommand provided - show interactive menu
-            show_welcome_menu().await;
-        }
-    }
-}
-
-async fn handle_update() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔍 Checking for updates...");
-    
-    let latest_release = get_latest_release().await?;
-    let latest_version = latest_release.tag_name.trim_start_matches('v');
-    
-    if version_compare(CURRENT_VERSION, latest_version) >= 0 {
-        println!("✅ You're already running the latest version ({})", CURRENT_VERSION);
-        return Ok(());
-    }
-    
-    println!("🆕 New version available: {} -> {}", CURRENT_VERSION, latest_version);
-    println!("📋 Release notes:\n{}", latest_release.body);
-    
-    let confirm = Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt("Do you want to update now?")
-        .default(true)
-        .interact()?;
-    
-    if !confirm {
-        println!("Update cancelled.");
-        return Ok(());
-    }
-    
-    // Download and install update
-    download_and_install_update(&latest_release).await?;
-    
-    println!("✅ Successfully updated to version {}!", latest_version);
-    println!("🔄 Please restart your terminal or run 'devgeini --version' to verify the update.");
-    
-    Ok(())
-}
-
-async fn check_for_updates() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔍 Checking for updates...");
-    
-    let latest_release = get_latest_release().await?;
-    let latest_version = latest_release.tag_name.trim_start_matches('v');
-    
-    println!("📦 Current version: {}", CURRENT_VERSION);
-    println!("🆕 Latest version: {}", latest_version);
-    
-    if version_compare(CURRENT_VERSION, latest_version) < 0 {
-        println!("🎉 A new version is available!");
-        println!("📋 Release notes:\n{}", latest_release.body);
-        println!("🚀 Run 'devgeini --update' to update to the latest version.");
-    } else {
-        println!("✅ You're running the latest version!");
-    }
-    
-    Ok(())
-}
-
-async fn check_for_updates_silent() -> Result<(), Box<dyn std::error::Error>> {
-    let latest_release = get_latest_release().await?;
-    let latest_version = latest_release.tag_name.trim_start_matches('v');
-    
-    if version_compare(CURRENT_VERSION, latest_version) < 0 {
-        println!("💡 A new version ({}) is available! Run 'devgeini --update' to upgrade.", latest_version);
-    }
-    
-    Ok(())
-}
-
-async fn get_latest_release() -> Result<GitHubRelease, Box<dyn std::error::Error>> {
-    let client = reqwest::Client::new();
-    let url = format!("https://api.github.com/repos/{}/releases/latest", GITHUB_REPO);
-    
-    let response = client
-        .get(&url)
-        .header("User-Agent", format!("devgeini/{}", CURRENT_VERSION))
-        .send()
-        .await?;
-    
-    if !response.status().is_success() {
-        return Err(format!("GitHub API request failed: {}", response.status()).into());
-    }
-    
-    let release: GitHubRelease = response.json().await?;
-    Ok(release)
-}
-
-async fn download_and_install_update(release: &GitHubRelease) -> Result<(), Box<dyn std::error::Error>> {
-    // Determine the correct asset for the current platform
-    let target_asset = find_matching_asset(&release.assets)?;
-    
-    println!("📥 Downloading {} ({} bytes)...", target_asset.name, target_asset.size);
-    
-    // Download the asset
-    let client = reqwest::Client::new();
-    let response = client
-        .get(&target_asset.browser_download_url)
-        .header("User-Agent", format!("devgeini/{}", CURRENT_VERSION))
-        .send()
-        .await?;
-    
-    if !response.status().is_success() {
-        return Err(format!("Failed to download update: {}", response.status()).into());
-    }
-    
-    let bytes = response.bytes().await?;
-    
-    // Get current executable path
-    let current_exe = std::env::current_exe()?;
-    let backup_path = current_exe.with_extension("bak");
-    
-    // Create backup of current executable
-    std::fs::copy(&current_exe, &backup_path)?;
-    println!("📁 Created backup at: {}", backup_path.display());
-    
-    // Write new executable
-    if target_asset.name.ends_with(".tar.gz") || target_asset.name.ends_with(".zip") {
-        // Handle compressed archives
-        extract_and_install_executable(&bytes, &current_exe, &target_asset.name).await?;
-    } else {
-        // Direct executable replacement
-        std::fs::write(&current_exe, &bytes)?;
-        
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut permissions = std::fs::metadata(&current_exe)?.permissions();
-            permissions.set_mode(0o755);
-            std::fs::set_permissions(&current_exe, permissions)?;
-        }
-    }
-    
-    println!("✅ Installation completed!");
-    
-    // Clean up backup on successful installation
-    if let Err(e) = std::fs::remove_file(&backup_path) {
-        println!("⚠️  Warning: Could not remove backup file: {}", e);
-    }
-    
-    Ok(())
-}
-
-fn find_matching_asset(assets: &[GitHubAsset]) -> Result<&GitHubAsset, Box<dyn std::error::Error>> {
-    let target_os = std::env::consts::OS;
-    let target_arch = std::env::consts::ARCH;
-    
-    // Define platform-specific patterns
-    let patterns = match (target_os, target_arch) {
-        ("windows", "x86_64") => vec!["windows", "win64", "x86_64-pc-windows"],
-        ("windows", "x86") => vec!["windows", "win32", "i686-pc-windows"],
-        ("macos", "x86_64") => vec!["macos", "darwin", "x86_64-apple-darwin"],
-        ("macos", "aarch64") => vec!["macos", "darwin", "aarch64-apple-darwin"],
-        ("linux", "x86_64") => vec!["linux", "x86_64-unknown-linux"],
-        ("linux", "aarch64") => vec!["linux", "aarch64-unknown-linux"],
-        _ => vec!["universal"],
-    };
-    
-    // Find matching asset
-    for asset in assets {
-        let asset_name_lower = asset.name.to_lowercase();
-        for pattern in &patterns {
-            if asset_name_lower.contains(pattern) {
-                return Ok(asset);
-            }
+        Some(("template", sub_matches)) => {
+            handle_template_command(sub_matches);
         }
-    }
-    
-    // Fallback to first asset if no specific match found
-    assets.first()
-        .ok_or_else(|| "No suitable release asset found for your platform".into())
-}
-
-async fn extract_and_install_executable(
-    bytes: &[u8], 
-    target_path: &Path, 
-    filename: &str
-) -> Result<(), Box<dyn std::error::Error>> {
-    use std::io::Read;
-    
-    if filename.ends_with(".tar.gz") {
-        // Handle tar.gz files
-        let tar = flate2::read::GzDecoder::new(bytes);
-        let mut archive = tar::Archive::new(tar);
-        
-        for entry in archive.entries()? {
-            let mut entry = entry?;
-            let path = entry.path()?;
-            
-            // Look for the executable (usually named 'devgeini' or similar)
-            if path.file_name().and_then(|s| s.to_str()).map_or(false, |s| s.starts_with("devgeini")) {
-                let mut buffer = Vec::new();
-                entry.read_to_end(&mut buffer)?;
-                std::fs::write(target_path, buffer)?;
-                
-                #[cfg(unix)]
-                {
-                    use std::os::unix::fs::PermissionsExt;
-                    let mut permissions = std::fs::metadata(target_path)?.permissions();
-                    permissions.set_mode(0o755);
-                    std::fs::set_permissions(target_path, permissions)?;
-                }
-                
-                return Ok(());
-            }
+        Some(("list", sub_matches)) => {
+            handle_list_command(sub_matches);
         }
-    } else if filename.ends_with(".zip") {
-        // Handle zip files (Windows typically)
-        let reader = std::io::Cursor::new(bytes);
-        let mut archive = zip::ZipArchive::new(reader)?;
-        
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i)?;
-            if file.name().ends_with(".exe") || file.name().ends_with("devgeini") {
-                let mut buffer = Vec::new();
-                std::io::copy(&mut file, &mut buffer)?;
-                std::fs::write(target_path, buffer)?;
-                return Ok(());
-            }
+        Some(("search", sub_matches)) => {
+            handle_search_command(sub_matches);
         }
-    }
-    
-    Err("Could not find executable in archive".into())
-}
-
-fn version_compare(current: &str, latest: &str) -> i32 {
-    let current_parts: Vec<u32> = current.split('.').filter_map(|s| s.parse().ok()).collect();
-    let latest_parts: Vec<u32> = latest.split('.').filter_map(|s| s.parse().ok()).collect();
-    
-    let max_len = current_parts.len().max(latest_parts.len());
-    
-    for i in 0..max_len {
-        let current_part = current_parts.get(i).unwrap_or(&0);
-        let latest_part = latest_parts.get(i).unwrap_or(&0);
-        
-        match current_part.cmp(latest_part) {
-            std::cmp::Ordering::Less => return -1,
-            std::cmp::Ordering::Greater => return 1,
-            std::cmp::Ordering::Equal => continue,
+        _ => {
+            // No subcommand provided - show interactive menu
+            show_welcome_menu().await;
         }
     }
-    
-    0
 }
 
 fn show_next_steps(config: &ProjectConfig) {
     match config.project_type {
         ProjectType::FullStackWeb => {
-            show_fullstack_instructions(config);
-        }
-        ProjectType::Frontend => {
-            show_frontend_instructions(config);
+            for stack in config.stacks() {
+                match stack.kind() {
+                    StackKind::Backend => println!("\n🔧 Backend Setup:"),
+                    StackKind::Frontend => println!("\n🎨 Frontend Setup:"),
+                }
+                print_steps(stack, Some(stack.kind().dir()));
+            }
+
+            println!("\n💡 Pro tip: Run backend and frontend in separate terminals!");
         }
-        ProjectType::Backend => {
-            show_backend_instructions(config);
+        ProjectType::Frontend | ProjectType::Backend => {
+            for stack in config.stacks() {
+                print_steps(stack, None);
+            }
         }
         ProjectType::CliTool => {
             println!("🦀 Build project: cargo build");
@@ -519,212 +791,13 @@ fn show_next_steps(config: &ProjectConfig) {
     }
 }
 
-fn show_fullstack_instructions(config: &ProjectConfig) {
-    // Backend instructions
-    if let Some(backend) = &config.backend_stack {
-        println!("\n🔧 Backend Setup:");
-        match backend {
-            BackendStack::NodeJs => {
-                println!("📦 Install backend deps: cd backend && npm install");
-                println!("🚀 Start backend: npm run dev (usually on port 3001)");
-            }
-            BackendStack::NodeJsTs => {
-                println!("📦 Install backend deps: cd backend && npm install");
-                println!("🚀 Start backend: npm run dev (TypeScript)");
-            }
-            BackendStack::Python => {
-                println!("🐍 Setup virtual env: cd backend && python -m venv venv");
-                println!("📦 Activate & install: source venv/bin/activate && pip install -r requirements.txt");
-                println!("🚀 Start backend: python app.py");
-            }
-            BackendStack::Rust => {
-                println!("🦀 Build backend: cd backend && cargo build");
-                println!("🚀 Start backend: cargo run");
-            }
-            BackendStack::Go => {
-                println!("📦 Install deps: cd backend && go mod tidy");
-                println!("🚀 Start backend: go run main.go");
-            }
-            BackendStack::Java => {
-                println!("☕ Build project: cd backend && mvn clean install");
-                println!("🚀 Start backend: mvn spring-boot:run");
-            }
-            BackendStack::Php => {
-                println!("🐘 Install deps: cd backend && composer install");
-                println!("🚀 Start backend: php -S localhost:8000");
-            }
-        }
-    }
-
-    // Frontend instructions
-    if let Some(frontend) = &config.frontend_stack {
-        println!("\n🎨 Frontend Setup:");
-        match frontend {
-            FrontendStack::React => {
-                println!("📦 Install frontend deps: cd frontend && npm install");
-                println!("🚀 Start frontend: npm start (usually on port 3000)");
-            }
-            FrontendStack::ReactTs => {
-                println!("📦 Install frontend deps: cd frontend && npm install");
-                println!("🚀 Start frontend: npm start (React + TypeScript)");
-            }
-            FrontendStack::Vue => {
-                println!("📦 Install frontend deps: cd frontend && npm install");
-                println!("🚀 Start frontend: npm run serve");
-            }
-            FrontendStack::VueTs => {
-                println!("📦 Install frontend deps: cd frontend && npm install");
-                println!("🚀 Start frontend: npm run serve (Vue + TypeScript)");
-            }
-            FrontendStack::Angular => {
-                println!("📦 Install frontend deps: cd frontend && npm install");
-                println!("🚀 Start frontend: ng serve");
-            }
-            FrontendStack::Svelte => {
-                println!("📦 Install frontend deps: cd frontend && npm install");
-                println!("🚀 Start frontend: npm run dev");
-            }
-            FrontendStack::SvelteTs => {
-                println!("📦 Install frontend deps: cd frontend && npm install");
-                println!("🚀 Start frontend: npm run dev (Svelte + TypeScript)");
-            }
-            FrontendStack::NextJs => {
-                println!("📦 Install frontend deps: cd frontend && npm install");
-                println!("🚀 Start frontend: npm run dev");
-            }
-            FrontendStack::NextJsTs => {
-                println!("📦 Install frontend deps: cd frontend && npm install");
-                println!("🚀 Start frontend: npm run dev (Next.js + TypeScript)");
-            }
-            FrontendStack::Vanilla => {
-                println!("📦 Install frontend deps: cd frontend && npm install");
-                println!("🚀 Start frontend: npm run dev");
-            }
-            FrontendStack::VanillaTs => {
-                println!("📦 Install frontend deps: cd frontend && npm install");
-                println!("🚀 Start frontend: npm run dev (Vanilla + TypeScript)");
-            }
-        }
-    }
-
-    println!("\n💡 Pro tip: Run backend and frontend in separate terminals!");
-}
-
-fn show_frontend_instructions(config: &ProjectConfig) {
-    if let Some(frontend) = &config.frontend_stack {
-        match frontend {
-            FrontendStack::React => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: npm start");
-                println!("🏗️  Build for production: npm run build");
-            }
-            FrontendStack::ReactTs => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: npm start (React + TypeScript)");
-                println!("🏗️  Build for production: npm run build");
-                println!("🔧 Type check: npm run type-check");
-            }
-            FrontendStack::Vue => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: npm run serve");
-                println!("🏗️  Build for production: npm run build");
-            }
-            FrontendStack::VueTs => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: npm run serve (Vue + TypeScript)");
-                println!("🏗️  Build for production: npm run build");
-                println!("🔧 Type check: npm run type-check");
-            }
-            FrontendStack::Angular => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: ng serve");
-                println!("🏗️  Build for production: ng build");
-                println!("🧪 Run tests: ng test");
-            }
-            FrontendStack::Svelte => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: npm run dev");
-                println!("🏗️  Build for production: npm run build");
-            }
-            FrontendStack::SvelteTs => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: npm run dev (Svelte + TypeScript)");
-                println!("🏗️  Build for production: npm run build");
-                println!("🔧 Type check: npm run check");
-            }
-            FrontendStack::NextJs => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: npm run dev");
-                println!("🏗️  Build for production: npm run build");
-                println!("🌐 Start production: npm start");
-            }
-            FrontendStack::NextJsTs => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: npm run dev (Next.js + TypeScript)");
-                println!("🏗️  Build for production: npm run build");
-                println!("🌐 Start production: npm start");
-                println!("🔧 Type check: npm run type-check");
-            }
-            FrontendStack::Vanilla => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: npm run dev");
-                println!("🏗️  Build for production: npm run build");
-            }
-            FrontendStack::VanillaTs => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: npm run dev (Vanilla + TypeScript)");
-                println!("🏗️  Build for production: npm run build");
-                println!("🔧 Type check: tsc --noEmit");
-            }
+// `subdir` prefixes the first step with a cd for full-stack layouts
+fn print_steps(stack: &dyn StackGenerator, subdir: Option<&str>) {
+    for (i, step) in stack.next_steps().iter().enumerate() {
+        match subdir {
+            Some(dir) if i == 0 => println!("{}: cd {} && {}", step.label, dir, step.command),
+            _ => println!("{}: {}", step.label, step.command),
         }
     }
+    println!("🌐 Runs on: http://localhost:{}", stack.dev_port());
 }
-
-fn show_backend_instructions(config: &ProjectConfig) {
-    if let Some(backend) = &config.backend_stack {
-        match backend {
-            BackendStack::NodeJs => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: npm run dev");
-                println!("🏗️  Start production: npm start");
-            }
-            BackendStack::NodeJsTs => {
-                println!("📦 Install dependencies: npm install");
-                println!("🚀 Start development: npm run dev (Node.js + TypeScript)");
-                println!("🏗️  Build project: npm run build");
-                println!("🌐 Start production: npm start");
-                println!("🔧 Type check: npm run type-check");
-            }
-            BackendStack::Python => {
-                println!("🐍 Create virtual environment: python -m venv venv");
-                println!("📦 Activate and install: source venv/bin/activate && pip install -r requirements.txt");
-                println!("🚀 Start development: python app.py");
-                println!("🧪 Run tests: pytest");
-            }
-            BackendStack::Rust => {
-                println!("📦 Build dependencies: cargo build");
-                println!("🚀 Start development: cargo run");
-                println!("🧪 Run tests: cargo test");
-                println!("🏗️  Build release: cargo build --release");
-            }
-            BackendStack::Go => {
-                println!("📦 Install dependencies: go mod tidy");
-                println!("🚀 Start development: go run main.go");
-                println!("🏗️  Build binary: go build");
-                println!("🧪 Run tests: go test");
-            }
-            BackendStack::Java => {
-                println!("📦 Install dependencies: mvn clean install");
-                println!("🚀 Start development: mvn spring-boot:run");
-                println!("🏗️  Build project: mvn clean package");
-                println!("🧪 Run tests: mvn test");
-            }
-            BackendStack::Php => {
-                println!("📦 Install dependencies: composer install");
-                println!("🚀 Start development: php -S localhost:8000");
-                println!("🧪 Run tests: vendor/bin/phpunit");
-                println!("📋 Check syntax: composer run-script lint");
-            }
-        }
-    }
-}
\ No newline at end of file
@@ -0,0 +1,580 @@
+use crate::config::structure::ProjectConfig;
+use crate::error::DevgeiniError;
+use crate::stacks::{EnvVar, NextStep, StackGenerator, StackKind};
+use crate::templates::engine::{render_dir, render_str};
+use crate::utils::writer::ProjectWriter;
+
+/// A stack whose files come from the template directories built into devgeini.
+#[derive(Debug, Clone, Copy)]
+pub struct BuiltinStack {
+    pub id: &'static str,
+    pub kind: StackKind,
+    pub display_name: &'static str,
+    pub description: &'static str,
+    pub language: &'static str,
+    /// Empty directories created before rendering
+    pub dirs: &'static [&'static str],
+    /// Template directories rendered into the stack's root, in order
+    pub templates: &'static [&'static str],
+    /// Template directory holding the Dockerfile
+    pub dockerfile: &'static str,
+    pub gitignore: &'static str,
+    /// Names and values of the `.env` variables; values may use template variables
+    pub env: &'static [(&'static str, &'static str)],
+    pub next_steps: &'static [NextStep],
+    pub dev_port: u16,
+}
+
+impl StackGenerator for BuiltinStack {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn kind(&self) -> StackKind {
+        self.kind
+    }
+
+    fn display_name(&self) -> &str {
+        self.display_name
+    }
+
+    fn description(&self) -> &str {
+        self.description
+    }
+
+    fn language(&self) -> &str {
+        self.language
+    }
+
+    fn write_files(&self, config: &ProjectConfig, writer: &mut dyn ProjectWriter) -> Result<(), DevgeiniError> {
+        for dir in self.dirs {
+            writer.create_dir_all(dir)?;
+        }
+        for templates in self.templates {
+            render_dir(templates, config, writer)?;
+        }
+        Ok(())
+    }
+
+    fn gitignore(&self) -> &str {
+        self.gitignore
+    }
+
+    fn write_dockerfile(&self, config: &ProjectConfig, writer: &mut dyn ProjectWriter) -> Result<(), DevgeiniError> {
+        render_dir(self.dockerfile, config, writer)
+    }
+
+    fn env_vars(&self, config: &ProjectConfig) -> Result<Vec<EnvVar>, DevgeiniError> {
+        self.env
+            .iter()
+            .map(|(name, value)| {
+                Ok(EnvVar {
+                    name: name.to_string(),
+                    value: render_str(value, config)?,
+                })
+            })
+            .collect()
+    }
+
+    fn next_steps(&self) -> &[NextStep] {
+        self.next_steps
+    }
+
+    fn dev_port(&self) -> u16 {
+        self.dev_port
+    }
+}
+
+const NPM_INSTALL: NextStep = NextStep::new("📦 Install dependencies", "npm install");
+const NPM_BUILD: NextStep = NextStep::new("🏗️  Build for production", "npm run build");
+
+const FRONTEND_DOCKERFILE: &str = "docker/frontend";
+const FRONTEND_GITIGNORE: &str = "# Build outputs\ndist/\nbuild/\n.next/\n# Coverage\ncoverage/";
+const VITE_ENV: &[(&str, &str)] = &[
+    ("VITE_API_URL", "http://localhost:{{ api_port }}"),
+    ("VITE_APP_NAME", "{{ name }}"),
+];
+const NEXT_ENV: &[(&str, &str)] = &[
+    ("NEXT_PUBLIC_API_URL", "http://localhost:{{ api_port }}"),
+    ("NEXT_PUBLIC_APP_NAME", "{{ name }}"),
+];
+const VITE_PORT: u16 = 5173;
+
+const NODE_ENV: &[(&str, &str)] = &[("PORT", "3001"), ("NODE_ENV", "development")];
+const JAVA_GITIGNORE: &str = "# Java\ntarget/\nbuild/\n.gradle/\n*.class\n*.jar";
+
+// Menus list stacks in this order, so the first frontend and backend are the default choices
+pub const BUILTIN_STACKS: &[BuiltinStack] = &[
+    BuiltinStack {
+        id: "react-ts",
+        kind: StackKind::Frontend,
+        display_name: "React (TypeScript)",
+        description: "React 18 single-page app on Vite with TypeScript",
+        language: "TypeScript",
+        dirs: &["public"],
+        templates: &["frontend/react-ts"],
+        dockerfile: FRONTEND_DOCKERFILE,
+        gitignore: FRONTEND_GITIGNORE,
+        env: VITE_ENV,
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm run dev"),
+            NPM_BUILD,
+            NextStep::new("🔧 Lint", "npm run lint"),
+        ],
+        dev_port: VITE_PORT,
+    },
+    BuiltinStack {
+        id: "react",
+        kind: StackKind::Frontend,
+        display_name: "React (JavaScript)",
+        description: "React 18 single-page app on Vite",
+        language: "JavaScript",
+        dirs: &["public"],
+        templates: &["frontend/react"],
+        dockerfile: FRONTEND_DOCKERFILE,
+        gitignore: FRONTEND_GITIGNORE,
+        env: VITE_ENV,
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm run dev"),
+            NPM_BUILD,
+        ],
+        dev_port: VITE_PORT,
+    },
+    BuiltinStack {
+        id: "vue-ts",
+        kind: StackKind::Frontend,
+        display_name: "Vue.js (TypeScript)",
+        description: "Vue 3 single-page app on Vite with TypeScript",
+        language: "TypeScript",
+        dirs: &[],
+        templates: &["frontend/vue-ts"],
+        dockerfile: FRONTEND_DOCKERFILE,
+        gitignore: FRONTEND_GITIGNORE,
+        env: VITE_ENV,
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm run dev"),
+            NextStep::new("🏗️  Type check and build", "npm run build"),
+        ],
+        dev_port: VITE_PORT,
+    },
+    BuiltinStack {
+        id: "vue",
+        kind: StackKind::Frontend,
+        display_name: "Vue.js (JavaScript)",
+        description: "Vue 3 single-page app on Vite",
+        language: "JavaScript",
+        dirs: &[],
+        templates: &["frontend/vue"],
+        dockerfile: FRONTEND_DOCKERFILE,
+        gitignore: FRONTEND_GITIGNORE,
+        env: VITE_ENV,
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm run dev"),
+            NPM_BUILD,
+        ],
+        dev_port: VITE_PORT,
+    },
+    BuiltinStack {
+        id: "angular",
+        kind: StackKind::Frontend,
+        display_name: "Angular (TypeScript)",
+        description: "Angular 18 application with the Angular CLI",
+        language: "TypeScript",
+        dirs: &["public"],
+        templates: &["frontend/angular"],
+        dockerfile: FRONTEND_DOCKERFILE,
+        gitignore: FRONTEND_GITIGNORE,
+        // Angular reads its settings from src/environments instead of .env
+        env: &[],
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm start"),
+            NPM_BUILD,
+            NextStep::new("🧪 Run tests", "npm test"),
+        ],
+        dev_port: 4200,
+    },
+    BuiltinStack {
+        id: "svelte-ts",
+        kind: StackKind::Frontend,
+        display_name: "Svelte (TypeScript)",
+        description: "Svelte 4 app on Vite with TypeScript",
+        language: "TypeScript",
+        dirs: &["public"],
+        templates: &["frontend/svelte-ts"],
+        dockerfile: FRONTEND_DOCKERFILE,
+        gitignore: FRONTEND_GITIGNORE,
+        env: VITE_ENV,
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm run dev"),
+            NPM_BUILD,
+            NextStep::new("🔧 Type check", "npm run check"),
+        ],
+        dev_port: VITE_PORT,
+    },
+    BuiltinStack {
+        id: "svelte",
+        kind: StackKind::Frontend,
+        display_name: "Svelte (JavaScript)",
+        description: "Svelte 4 app on Vite",
+        language: "JavaScript",
+        dirs: &[],
+        templates: &["frontend/svelte"],
+        dockerfile: FRONTEND_DOCKERFILE,
+        gitignore: FRONTEND_GITIGNORE,
+        env: VITE_ENV,
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm run dev"),
+            NPM_BUILD,
+        ],
+        dev_port: VITE_PORT,
+    },
+    BuiltinStack {
+        id: "nextjs-ts",
+        kind: StackKind::Frontend,
+        display_name: "Next.js (TypeScript)",
+        description: "Next.js 13 app with the pages router and TypeScript",
+        language: "TypeScript",
+        dirs: &["public"],
+        templates: &["frontend/nextjs-ts"],
+        dockerfile: FRONTEND_DOCKERFILE,
+        gitignore: FRONTEND_GITIGNORE,
+        env: NEXT_ENV,
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm run dev"),
+            NPM_BUILD,
+            NextStep::new("🌐 Start production", "npm start"),
+            NextStep::new("🔧 Lint", "npm run lint"),
+        ],
+        dev_port: 3000,
+    },
+    BuiltinStack {
+        id: "nextjs",
+        kind: StackKind::Frontend,
+        display_name: "Next.js (JavaScript)",
+        description: "Next.js 13 app with the pages router",
+        language: "JavaScript",
+        dirs: &["public"],
+        templates: &["frontend/nextjs"],
+        dockerfile: FRONTEND_DOCKERFILE,
+        gitignore: FRONTEND_GITIGNORE,
+        env: NEXT_ENV,
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm run dev"),
+            NPM_BUILD,
+            NextStep::new("🌐 Start production", "npm start"),
+        ],
+        dev_port: 3000,
+    },
+    BuiltinStack {
+        id: "vanilla-ts",
+        kind: StackKind::Frontend,
+        display_name: "Vanilla TypeScript",
+        description: "Framework-free page on Vite with TypeScript",
+        language: "TypeScript",
+        dirs: &["public"],
+        templates: &["frontend/vanilla-ts"],
+        dockerfile: FRONTEND_DOCKERFILE,
+        gitignore: FRONTEND_GITIGNORE,
+        env: VITE_ENV,
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm run dev"),
+            NPM_BUILD,
+            NextStep::new("🔧 Type check", "npm run type-check"),
+        ],
+        dev_port: VITE_PORT,
+    },
+    BuiltinStack {
+        id: "vanilla",
+        kind: StackKind::Frontend,
+        display_name: "Vanilla JavaScript",
+        description: "Framework-free page on Vite",
+        language: "JavaScript",
+        dirs: &[],
+        templates: &["frontend/vanilla"],
+        dockerfile: FRONTEND_DOCKERFILE,
+        gitignore: FRONTEND_GITIGNORE,
+        env: VITE_ENV,
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm run dev"),
+            NPM_BUILD,
+        ],
+        dev_port: VITE_PORT,
+    },
+    BuiltinStack {
+        id: "node-ts",
+        kind: StackKind::Backend,
+        display_name: "Node.js/Express (TypeScript)",
+        description: "Express REST API with TypeScript",
+        language: "TypeScript",
+        dirs: &["src/controllers", "dist"],
+        templates: &["backend/node-ts"],
+        dockerfile: "docker/backend/node-ts",
+        gitignore: "",
+        env: NODE_ENV,
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm run dev"),
+            NextStep::new("🏗️  Build project", "npm run build"),
+            NextStep::new("🌐 Start production", "npm start"),
+        ],
+        dev_port: 3001,
+    },
+    BuiltinStack {
+        id: "node",
+        kind: StackKind::Backend,
+        display_name: "Node.js/Express (JavaScript)",
+        description: "Express REST API",
+        language: "JavaScript",
+        dirs: &["controllers", "middleware", "routes"],
+        templates: &["backend/node"],
+        dockerfile: "docker/backend/node",
+        gitignore: "",
+        env: NODE_ENV,
+        next_steps: &[
+            NPM_INSTALL,
+            NextStep::new("🚀 Start development", "npm run dev"),
+            NextStep::new("🌐 Start production", "npm start"),
+        ],
+        dev_port: 3001,
+    },
+    BuiltinStack {
+        id: "python",
+        kind: StackKind::Backend,
+        display_name: "Python (FastAPI)",
+        description: "FastAPI service run with uvicorn",
+        language: "Python",
+        dirs: &["routers", "models"],
+        templates: &["backend/python"],
+        dockerfile: "docker/backend/python",
+        gitignore: "# Python\n__pycache__/\n*.pyc\n*.pyo\n*.pyd\nvenv/\n.venv/",
+        env: &[("PORT", "8000")],
+        next_steps: &[
+            NextStep::new("🐍 Create virtual environment", "python -m venv venv"),
+            NextStep::new("📦 Activate and install", "source venv/bin/activate && pip install -r requirements.txt"),
+            NextStep::new("🚀 Start development", "uvicorn main:app --reload"),
+        ],
+        dev_port: 8000,
+    },
+    BuiltinStack {
+        id: "rust",
+        kind: StackKind::Backend,
+        display_name: "Rust (Actix)",
+        description: "Actix Web service on Tokio",
+        language: "Rust",
+        dirs: &[],
+        templates: &["backend/rust"],
+        dockerfile: "docker/backend/rust",
+        gitignore: "# Rust\ntarget/\nCargo.lock",
+        env: &[("PORT", "8080")],
+        next_steps: &[
+            NextStep::new("📦 Build dependencies", "cargo build"),
+            NextStep::new("🚀 Start development", "cargo run"),
+            NextStep::new("🧪 Run tests", "cargo test"),
+            NextStep::new("🏗️  Build release", "cargo build --release"),
+        ],
+        dev_port: 8080,
+    },
+    BuiltinStack {
+        id: "go",
+        kind: StackKind::Backend,
+        display_name: "Go (net/http)",
+        description: "HTTP service using only the standard library",
+        language: "Go",
+        dirs: &[],
+        templates: &["backend/go"],
+        dockerfile: "docker/backend/go",
+        gitignore: "# Go\nbin/\n*.exe\n*.test\n*.out\nvendor/",
+        env: &[("PORT", "8080")],
+        next_steps: &[
+            NextStep::new("📦 Install dependencies", "go mod tidy"),
+            NextStep::new("🚀 Start development", "go run ./cmd/server"),
+            NextStep::new("🏗️  Build binary", "go build -o bin/server ./cmd/server"),
+            NextStep::new("🧪 Run tests", "go test ./..."),
+        ],
+        dev_port: 8080,
+    },
+    BuiltinStack {
+        id: "java",
+        kind: StackKind::Backend,
+        display_name: "Java (Spring Boot, Maven)",
+        description: "Spring Boot REST API built with Maven",
+        language: "Java",
+        dirs: &[],
+        // Sources are shared, only the build files differ between Maven and Gradle
+        templates: &["backend/spring-boot", "backend/java"],
+        dockerfile: "docker/backend/java",
+        gitignore: JAVA_GITIGNORE,
+        env: &[("PORT", "8080")],
+        next_steps: &[
+            NextStep::new("📦 Install dependencies", "mvn clean install"),
+            NextStep::new("🚀 Start development", "mvn spring-boot:run"),
+            NextStep::new("🏗️  Build project", "mvn clean package"),
+            NextStep::new("🧪 Run tests", "mvn test"),
+        ],
+        dev_port: 8080,
+    },
+    BuiltinStack {
+        id: "java-gradle",
+        kind: StackKind::Backend,
+        display_name: "Java (Spring Boot, Gradle)",
+        description: "Spring Boot REST API built with Gradle",
+        language: "Java",
+        dirs: &[],
+        templates: &["backend/spring-boot", "backend/java-gradle"],
+        dockerfile: "docker/backend/java-gradle",
+        gitignore: JAVA_GITIGNORE,
+        env: &[("PORT", "8080")],
+        next_steps: &[
+            NextStep::new("📦 Install dependencies", "gradle build"),
+            NextStep::new("🚀 Start development", "gradle bootRun"),
+            NextStep::new("🏗️  Build project", "gradle bootJar"),
+            NextStep::new("🧪 Run tests", "gradle test"),
+        ],
+        dev_port: 8080,
+    },
+    BuiltinStack {
+        id: "php",
+        kind: StackKind::Backend,
+        display_name: "PHP",
+        description: "Dependency-free PHP API with a small router and PHPUnit",
+        language: "PHP",
+        dirs: &[],
+        templates: &["backend/php"],
+        dockerfile: "docker/backend/php",
+        gitignore: "# PHP\nvendor/\n.phpunit.cache/\n.phpunit.result.cache",
+        env: &[("PORT", "8000")],
+        next_steps: &[
+            NextStep::new("📦 Install dependencies", "composer install"),
+            NextStep::new("🚀 Start development", "php -S localhost:8000 -t public"),
+            NextStep::new("🧪 Run tests", "vendor/bin/phpunit"),
+            NextStep::new("📋 Check syntax", "composer run-script lint"),
+        ],
+        dev_port: 8000,
+    },
+];
+
+/// The built-in stack of `kind` called `id`.
+pub fn builtin_stack(kind: StackKind, id: &str) -> Option<&'static BuiltinStack> {
+    BUILTIN_STACKS.iter().find(|stack| stack.kind == kind && stack.id == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::structure::ProjectType;
+    use crate::utils::writer::MemoryWriter;
+    use std::collections::BTreeMap;
+
+    // The files of a single-stack `my-shop` project, by path with `/` separators
+    fn project_files(project_type: ProjectType, id: &str) -> BTreeMap<String, String> {
+        let mut config = ProjectConfig::new("my-shop".to_string(), project_type);
+        match project_type {
+            ProjectType::Frontend => config.frontend_stack = Some(id.parse().unwrap()),
+            _ => config.backend_stack = Some(id.parse().unwrap()),
+        }
+
+        let mut writer = MemoryWriter::new();
+        crate::generate(&config, &mut writer).unwrap();
+        writer
+            .files()
+            .map(|(path, contents)| {
+                let path = path.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+                (path, String::from_utf8_lossy(contents).into_owned())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn go_backend_uses_one_module_path() {
+        let files = project_files(ProjectType::Backend, "go");
+
+        assert!(files["go.mod"].starts_with("module my-shop-backend\n"), "{}", files["go.mod"]);
+        assert!(files["cmd/server/main.go"].contains("\"my-shop-backend/internal/server\""));
+        assert!(files["internal/handlers/health_test.go"].starts_with("package handlers"));
+        assert!(files["Dockerfile"].contains("go build"), "{}", files["Dockerfile"]);
+        assert!(files[".env.example"].contains("PORT=8080"), "{}", files[".env.example"]);
+    }
+
+    #[test]
+    fn java_backends_share_the_spring_boot_sources() {
+        let source = "src/main/java/com/example/myshop/MyShopApplication.java";
+        for (id, build_file) in [("java", "pom.xml"), ("java-gradle", "build.gradle.kts")] {
+            let files = project_files(ProjectType::Backend, id);
+
+            assert!(files[source].starts_with("package com.example.myshop;"), "{}", files[source]);
+            assert!(files.contains_key("src/test/java/com/example/myshop/controller/HealthControllerTest.java"));
+            assert!(files[build_file].contains("my-shop-backend"), "{}", files[build_file]);
+        }
+
+        let files = project_files(ProjectType::Backend, "java-gradle");
+        assert!(!files.contains_key("pom.xml"));
+        assert!(files["Dockerfile"].contains("gradle bootJar"), "{}", files["Dockerfile"]);
+    }
+
+    #[test]
+    fn php_backend_is_a_valid_composer_project() {
+        let files = project_files(ProjectType::Backend, "php");
+
+        let composer: serde_json::Value = serde_json::from_str(&files["composer.json"]).unwrap();
+        assert_eq!(composer["name"], "app/my-shop-backend");
+        assert_eq!(composer["autoload"]["psr-4"]["App\\"], "app/");
+        assert!(files["public/index.php"].contains("new Router()"));
+        assert!(files["Dockerfile"].contains("COPY docker/nginx.conf"), "{}", files["Dockerfile"]);
+        assert!(files.contains_key("docker/nginx.conf"));
+    }
+
+    #[test]
+    fn angular_build_output_matches_the_dockerfile() {
+        let files = project_files(ProjectType::Frontend, "angular");
+
+        let workspace: serde_json::Value = serde_json::from_str(&files["angular.json"]).unwrap();
+        let build = &workspace["projects"]["my-shop"]["architect"]["build"];
+        assert_eq!(build["options"]["outputPath"], "dist/my-shop");
+        assert!(files["Dockerfile"].contains("/app/dist/my-shop/browser "), "{}", files["Dockerfile"]);
+        assert!(!files[".env.example"].contains("VITE_"), "{}", files[".env.example"]);
+        assert!(files.contains_key("src/app/app.component.spec.ts"));
+    }
+
+    #[test]
+    fn typescript_variants_load_typescript_entry_points() {
+        for id in ["svelte-ts", "vanilla-ts"] {
+            let files = project_files(ProjectType::Frontend, id);
+
+            assert!(files["index.html"].contains("src=\"/src/main.ts\""), "{}: {}", id, files["index.html"]);
+            assert!(files.contains_key("tsconfig.json"), "{}", id);
+            assert!(!files.keys().any(|path| path.ends_with(".js") && path.starts_with("src/")), "{}", id);
+            let package: serde_json::Value = serde_json::from_str(&files["package.json"]).unwrap();
+            assert!(package["devDependencies"]["typescript"].is_string(), "{}", id);
+        }
+
+        let files = project_files(ProjectType::Frontend, "svelte-ts");
+        assert!(files["src/App.svelte"].starts_with("<script lang=\"ts\">"));
+    }
+
+    #[test]
+    fn readme_shows_the_table_next_steps() {
+        for stack in BUILTIN_STACKS {
+            let project_type = match stack.kind {
+                StackKind::Frontend => ProjectType::Frontend,
+                StackKind::Backend => ProjectType::Backend,
+            };
+            let readme = &project_files(project_type, stack.id)["README.md"];
+
+            assert!(readme.contains(stack.display_name), "{}: {}", stack.id, readme);
+            for step in stack.next_steps {
+                assert!(readme.contains(&*step.command), "{} is missing '{}'", stack.id, step.command);
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+pub mod builtin;
+pub mod plugin;
+pub mod registry;
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+use crate::config::structure::{ProjectConfig, ProjectType};
+use crate::error::DevgeiniError;
+use crate::utils::writer::ProjectWriter;
+
+/// Whether a stack is the frontend or the backend half of a project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StackKind {
+    Frontend,
+    Backend,
+}
+
+impl StackKind {
+    /// Directory the stack lives in within a full-stack project
+    pub fn dir(&self) -> &'static str {
+        match self {
+            StackKind::Frontend => "frontend",
+            StackKind::Backend => "backend",
+        }
+    }
+
+    pub fn project_types(&self) -> &'static [ProjectType] {
+        match self {
+            StackKind::Frontend => &[ProjectType::FullStackWeb, ProjectType::Frontend],
+            StackKind::Backend => &[ProjectType::FullStackWeb, ProjectType::Backend],
+        }
+    }
+}
+
+/// One line of the "next steps" shown after a project is generated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NextStep {
+    pub label: Cow<'static, str>,
+    pub command: Cow<'static, str>,
+}
+
+impl NextStep {
+    pub const fn new(label: &'static str, command: &'static str) -> Self {
+        NextStep {
+            label: Cow::Borrowed(label),
+            command: Cow::Borrowed(command),
+        }
+    }
+}
+
+/// A variable written to the generated `.env` and `.env.example`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvVar {
+    pub name: String,
+    pub value: String,
+}
+
+/// Everything devgeini needs from a frontend or backend stack: how to offer it in menus and
+/// `devgeini list`, the files it generates and what it adds to the shared project files.
+///
+/// Stacks are looked up by `id` in the `StackRegistry`. devgeini implements this for its
+/// built-in stacks and for the stacks of `devgeini-plugin-*` executables; library users can
+/// register their own with `StackRegistry::register`.
+pub trait StackGenerator: Send + Sync {
+    /// Name used with `--frontend` / `--backend` and in `project_config.toml`, e.g. `react-ts`
+    fn id(&self) -> &str;
+
+    fn kind(&self) -> StackKind;
+
+    fn display_name(&self) -> &str;
+
+    fn description(&self) -> &str;
+
+    fn language(&self) -> &str;
+
+    fn project_types(&self) -> &[ProjectType] {
+        self.kind().project_types()
+    }
+
+    /// Writes the stack's sources, relative to its own root (`frontend/` in full-stack projects).
+    fn write_files(&self, config: &ProjectConfig, writer: &mut dyn ProjectWriter) -> Result<(), DevgeiniError>;
+
+    /// Lines appended to the project's `.gitignore`
+    fn gitignore(&self) -> &str;
+
+    /// Writes a Dockerfile (and anything it needs) next to the stack's files.
+    fn write_dockerfile(&self, config: &ProjectConfig, writer: &mut dyn ProjectWriter) -> Result<(), DevgeiniError>;
+
+    fn env_vars(&self, config: &ProjectConfig) -> Result<Vec<EnvVar>, DevgeiniError>;
+
+    fn next_steps(&self) -> &[NextStep];
+
+    /// Port the development server listens on
+    fn dev_port(&self) -> u16;
+}
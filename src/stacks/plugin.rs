@@ -0,0 +1,517 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::naming::ProjectIdentifiers;
+use crate::config::structure::ProjectConfig;
+use crate::error::DevgeiniError;
+use crate::stacks::registry::StackRegistry;
+use crate::stacks::{EnvVar, NextStep, StackGenerator, StackKind};
+use crate::templates::engine::render_str;
+use crate::utils::writer::{is_inside_project, ProjectWriter};
+
+/// Executables whose file name starts with this are devgeini plugins.
+pub const PLUGIN_PREFIX: &str = "devgeini-plugin-";
+
+/// How long a plugin may take to list its stacks before it is stopped.
+pub const DESCRIBE_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a plugin may take to generate a stack's files before it is stopped.
+pub const GENERATE_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub fn plugins_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config").join("devgeini").join("plugins"))
+}
+
+/// Plugin executables in the plugins directory, then on `PATH`. When two have the same
+/// file name the first one wins, like the shell would pick it.
+pub fn discover_plugins() -> Vec<PathBuf> {
+    let mut search_dirs: Vec<PathBuf> = plugins_dir().into_iter().collect();
+    if let Some(path) = std::env::var_os("PATH") {
+        search_dirs.extend(std::env::split_paths(&path));
+    }
+    find_plugins(search_dirs)
+}
+
+fn find_plugins(search_dirs: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut seen = HashSet::new();
+    let mut plugins = Vec::new();
+    for dir in search_dirs {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut found: Vec<_> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| plugin_name(path).is_some() && is_executable(path))
+            .collect();
+        found.sort();
+
+        for path in found {
+            if seen.insert(path.file_name().map(|name| name.to_os_string())) {
+                plugins.push(path);
+            }
+        }
+    }
+    plugins
+}
+
+/// Asks every discovered plugin for its stacks and adds them after the ones already in `registry`.
+///
+/// Plugins that fail to answer, or offer a stack id that is already taken, are left out so one
+/// broken plugin does not stop devgeini from starting. The returned warnings say why.
+pub fn register_plugins(registry: &mut StackRegistry) -> Vec<String> {
+    register_plugins_from(registry, discover_plugins())
+}
+
+fn register_plugins_from(registry: &mut StackRegistry, plugins: Vec<PathBuf>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for plugin in plugins {
+        let name = plugin_name(&plugin).unwrap_or_default().to_string();
+        let description: Description = match call_plugin(&plugin, &name, &Request::Describe) {
+            Ok(description) => description,
+            Err(e) => {
+                warnings.push(format!("{}, skipping it", e));
+                continue;
+            }
+        };
+
+        for info in description.stacks {
+            let stack = PluginStack {
+                plugin: plugin.clone(),
+                name: name.clone(),
+                info,
+            };
+            if let Err(e) = registry.register(Box::new(stack)) {
+                warnings.push(format!("skipping a stack of plugin '{}': {}", name, e));
+            }
+        }
+    }
+    warnings
+}
+
+// What devgeini sends on a plugin's stdin, one request per run
+#[derive(Serialize)]
+#[serde(tag = "command", rename_all = "lowercase")]
+enum Request<'a> {
+    Describe,
+    Generate {
+        stack: &'a str,
+        kind: StackKind,
+        part: Part,
+        config: &'a ProjectConfig,
+        identifiers: &'a ProjectIdentifiers,
+    },
+}
+
+impl Request<'_> {
+    // Describing stacks should be instant; generating may run a scaffolder or fetch something
+    fn timeout(&self) -> Duration {
+        match self {
+            Request::Describe => DESCRIBE_TIMEOUT,
+            Request::Generate { .. } => GENERATE_TIMEOUT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Part {
+    Files,
+    Dockerfile,
+}
+
+// The answer to `describe`
+#[derive(Deserialize)]
+struct Description {
+    stacks: Vec<StackInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StackInfo {
+    id: String,
+    kind: StackKind,
+    display_name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    language: String,
+    #[serde(default)]
+    gitignore: String,
+    /// Values may use template variables, like those of the built-in stacks
+    #[serde(default)]
+    env: Vec<EnvVar>,
+    #[serde(default)]
+    next_steps: Vec<NextStep>,
+    dev_port: u16,
+}
+
+// The answer to `generate`
+#[derive(Deserialize)]
+struct Generated {
+    /// Directories to create even when no file ends up in them
+    #[serde(default)]
+    directories: Vec<PathBuf>,
+    #[serde(default)]
+    files: Vec<GeneratedFile>,
+}
+
+#[derive(Deserialize)]
+struct GeneratedFile {
+    path: PathBuf,
+    contents: String,
+    #[serde(default)]
+    encoding: Encoding,
+}
+
+// JSON strings cannot hold arbitrary bytes, so binary files such as images come as base64
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Encoding {
+    #[default]
+    #[serde(rename = "utf-8")]
+    Utf8,
+    Base64,
+}
+
+/// A stack provided by a `devgeini-plugin-*` executable.
+#[derive(Debug)]
+pub struct PluginStack {
+    plugin: PathBuf,
+    /// The plugin's file name without the prefix, used in messages
+    name: String,
+    info: StackInfo,
+}
+
+impl PluginStack {
+    fn generate(&self, part: Part, config: &ProjectConfig, writer: &mut dyn ProjectWriter) -> Result<(), DevgeiniError> {
+        let request = Request::Generate {
+            stack: &self.info.id,
+            kind: self.info.kind,
+            part,
+            config,
+            identifiers: &config.identifiers,
+        };
+        let generated: Generated = call_plugin(&self.plugin, &self.name, &request)?;
+
+        for dir in &generated.directories {
+            check_relative(&self.name, dir)?;
+            writer.create_dir_all(dir)?;
+        }
+        for file in &generated.files {
+            check_relative(&self.name, &file.path)?;
+            match file.encoding {
+                Encoding::Utf8 => writer.write(&file.path, &file.contents)?,
+                Encoding::Base64 => {
+                    let contents = BASE64.decode(&file.contents).map_err(|e| DevgeiniError::Plugin {
+                        plugin: self.name.clone(),
+                        message: format!("sent '{}' with invalid base64: {}", file.path.display(), e),
+                    })?;
+                    writer.write(&file.path, contents)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StackGenerator for PluginStack {
+    fn id(&self) -> &str {
+        &self.info.id
+    }
+
+    fn kind(&self) -> StackKind {
+        self.info.kind
+    }
+
+    fn display_name(&self) -> &str {
+        &self.info.display_name
+    }
+
+    fn description(&self) -> &str {
+        &self.info.description
+    }
+
+    fn language(&self) -> &str {
+        &self.info.language
+    }
+
+    fn write_files(&self, config: &ProjectConfig, writer: &mut dyn ProjectWriter) -> Result<(), DevgeiniError> {
+        self.generate(Part::Files, config, writer)
+    }
+
+    fn gitignore(&self) -> &str {
+        &self.info.gitignore
+    }
+
+    fn write_dockerfile(&self, config: &ProjectConfig, writer: &mut dyn ProjectWriter) -> Result<(), DevgeiniError> {
+        self.generate(Part::Dockerfile, config, writer)
+    }
+
+    fn env_vars(&self, config: &ProjectConfig) -> Result<Vec<EnvVar>, DevgeiniError> {
+        self.info
+            .env
+            .iter()
+            .map(|var| {
+                Ok(EnvVar {
+                    name: var.name.clone(),
+                    value: render_str(&var.value, config)?,
+                })
+            })
+            .collect()
+    }
+
+    fn next_steps(&self) -> &[NextStep] {
+        &self.info.next_steps
+    }
+
+    fn dev_port(&self) -> u16 {
+        self.info.dev_port
+    }
+}
+
+fn call_plugin<T: for<'de> Deserialize<'de>>(plugin: &Path, name: &str, request: &Request) -> Result<T, DevgeiniError> {
+    call_plugin_within(plugin, name, request, request.timeout())
+}
+
+// Runs the plugin with `request` as JSON on stdin and parses the JSON it prints.
+// Its stderr is passed through so plugins can report progress and problems themselves.
+fn call_plugin_within<T: for<'de> Deserialize<'de>>(
+    plugin: &Path,
+    name: &str,
+    request: &Request,
+    timeout: Duration,
+) -> Result<T, DevgeiniError> {
+    let plugin_error = |message: String| DevgeiniError::Plugin {
+        plugin: name.to_string(),
+        message,
+    };
+
+    let input = serde_json::to_vec(request).map_err(|e| plugin_error(format!("could not encode the request: {}", e)))?;
+    let mut child = Command::new(plugin)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| DevgeiniError::io(plugin, e))?;
+
+    let deadline = Instant::now() + timeout;
+    let stopped = || plugin_error(format!("did not answer within {} seconds and was stopped", timeout.as_secs()));
+
+    // The request is written and the answer read on their own threads, so neither a plugin that
+    // never reads its stdin nor one that fills the stdout pipe can hold devgeini past the deadline
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let (written_tx, written) = mpsc::channel();
+    thread::spawn(move || {
+        let result = match stdin.write_all(&input) {
+            // A plugin that does not need the request may exit without reading it
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+            result => result,
+        };
+        let _ = written_tx.send(result);
+    });
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let (answer_tx, answer) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = answer_tx.send(stdout.read_to_end(&mut buffer).map(|_| buffer));
+    });
+
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| DevgeiniError::io(plugin, e))? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(stopped());
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    if !status.success() {
+        return Err(plugin_error(format!("exited with {}", status)));
+    }
+    // A process the plugin started in the background may still hold the pipes open. Its
+    // threads are then left behind instead of waiting for it past the deadline.
+    let remaining = || deadline.saturating_duration_since(Instant::now());
+    written
+        .recv_timeout(remaining())
+        .map_err(|_| stopped())?
+        .map_err(|e| DevgeiniError::io(plugin, e))?;
+    let answer = answer
+        .recv_timeout(remaining())
+        .map_err(|_| stopped())?
+        .map_err(|e| DevgeiniError::io(plugin, e))?;
+    serde_json::from_slice(&answer).map_err(|e| plugin_error(format!("printed an invalid answer: {}", e)))
+}
+
+// Plugins may only write inside the stack's directory
+fn check_relative(name: &str, path: &Path) -> Result<(), DevgeiniError> {
+    if is_inside_project(path) && !path.as_os_str().is_empty() {
+        return Ok(());
+    }
+    Err(DevgeiniError::Plugin {
+        plugin: name.to_string(),
+        message: format!("wrote '{}', which is outside the project", path.display()),
+    })
+}
+
+fn plugin_name(path: &Path) -> Option<&str> {
+    let file_name = path.file_name()?.to_str()?;
+    let name = file_name.strip_prefix(PLUGIN_PREFIX)?;
+    // Windows executables carry an extension that is not part of the name
+    let name = name.strip_suffix(".exe").unwrap_or(name);
+    (!name.is_empty()).then_some(name)
+}
+
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = fs::metadata(path) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::config::structure::{FrontendStack, ProjectType};
+    use crate::utils::writer::MemoryWriter;
+    use std::os::unix::fs::PermissionsExt;
+
+    const DESCRIBE_ELM: &str =
+        r#"{"stacks": [{"id": "elm", "kind": "frontend", "display_name": "Elm", "dev_port": 8000}]}"#;
+
+    fn write_plugin(dir: &Path, name: &str, script: &str) -> PathBuf {
+        let path = dir.join(format!("{}{}", PLUGIN_PREFIX, name));
+        fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    // Answers `describe` with `description` and every other request with `generated`
+    fn answering_plugin(dir: &Path, description: &str, generated: &str) -> PathBuf {
+        let script = format!(
+            "request=$(cat)\ncase \"$request\" in\n  *'\"describe\"'*) echo '{}' ;;\n  *) echo '{}' ;;\nesac",
+            description, generated
+        );
+        write_plugin(dir, "elm", &script)
+    }
+
+    fn plugin_error(result: Result<Description, DevgeiniError>) -> String {
+        match result {
+            Err(DevgeiniError::Plugin { plugin, message }) => {
+                assert_eq!(plugin, "test");
+                message
+            }
+            Err(e) => panic!("expected a plugin error, got {:?}", e),
+            Ok(_) => panic!("expected a plugin error"),
+        }
+    }
+
+    #[test]
+    fn first_plugin_with_a_name_wins() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        let elm = write_plugin(first.path(), "elm", "exit 0");
+        write_plugin(second.path(), "elm", "exit 0");
+        let solid = write_plugin(second.path(), "solid", "exit 0");
+        // Neither a file that cannot be run nor another tool is a plugin
+        let disabled = write_plugin(second.path(), "disabled", "exit 0");
+        fs::set_permissions(&disabled, fs::Permissions::from_mode(0o644)).unwrap();
+        fs::write(second.path().join("devgeini"), "").unwrap();
+
+        let plugins = find_plugins(vec![first.path().to_path_buf(), second.path().to_path_buf()]);
+        assert_eq!(plugins, [elm, solid]);
+    }
+
+    #[test]
+    fn describe_and_generate_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        // "AAEC/w==" is the bytes 0, 1, 2 and 255
+        let generated = r#"{"directories": ["static"], "files": [
+            {"path": "src/Main.elm", "contents": "main = text \"hi\""},
+            {"path": "static/logo.bin", "contents": "AAEC/w==", "encoding": "base64"}]}"#
+            .replace('\n', " ");
+        let plugin = answering_plugin(dir.path(), DESCRIBE_ELM, &generated);
+
+        let mut registry = StackRegistry::new();
+        let warnings = register_plugins_from(&mut registry, vec![plugin]);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        let elm = registry.frontend("elm").unwrap();
+        assert!(matches!(elm, FrontendStack::Plugin(_)));
+        assert_eq!(elm.generator().display_name(), "Elm");
+
+        let mut config = ProjectConfig::new("shop".to_string(), ProjectType::Frontend);
+        config.frontend_stack = Some(elm.clone());
+        let mut writer = MemoryWriter::new();
+        elm.generator().write_files(&config, &mut writer).unwrap();
+
+        assert_eq!(writer.file_names(), ["src/Main.elm", "static/logo.bin"]);
+        let files: std::collections::BTreeMap<_, _> = writer.files().collect();
+        assert_eq!(files[Path::new("static/logo.bin")], [0, 1, 2, 255]);
+    }
+
+    #[test]
+    fn taken_stack_id_is_a_warning() {
+        let dir = tempfile::tempdir().unwrap();
+        let description = r#"{"stacks": [{"id": "react-ts", "kind": "frontend", "display_name": "Mine", "dev_port": 1}]}"#;
+        let plugin = answering_plugin(dir.path(), description, "{}");
+
+        let mut registry = StackRegistry::with_builtins();
+        let warnings = register_plugins_from(&mut registry, vec![plugin]);
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("already registered"), "{}", warnings[0]);
+        assert_eq!(registry.get(StackKind::Frontend, "react-ts").unwrap().display_name(), "React (TypeScript)");
+    }
+
+    #[test]
+    fn failing_plugins_are_errors() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let failing = write_plugin(dir.path(), "failing", "exit 3");
+        let message = plugin_error(call_plugin(&failing, "test", &Request::Describe));
+        assert!(message.contains("exited with"), "{}", message);
+
+        let chatty = write_plugin(dir.path(), "chatty", "echo 'Describing my stacks...'");
+        let message = plugin_error(call_plugin(&chatty, "test", &Request::Describe));
+        assert!(message.contains("invalid answer"), "{}", message);
+    }
+
+    #[test]
+    fn slow_plugin_is_stopped() {
+        let dir = tempfile::tempdir().unwrap();
+        // Never reads its request nor answers
+        let slow = write_plugin(dir.path(), "slow", "sleep 10");
+
+        let started = Instant::now();
+        let result = call_plugin_within(&slow, "test", &Request::Describe, Duration::from_millis(300));
+        let message = plugin_error(result);
+        assert!(message.contains("did not answer"), "{}", message);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn plugins_only_write_inside_their_stack() {
+        for path in ["../x", "/etc/passwd", ""] {
+            assert!(check_relative("test", Path::new(path)).is_err(), "{:?}", path);
+        }
+        assert!(check_relative("test", Path::new("src/main.elm")).is_ok());
+    }
+}
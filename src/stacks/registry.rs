@@ -0,0 +1,214 @@
+use std::sync::Arc;
+
+use crate::config::structure::{BackendStack, FrontendStack};
+use crate::error::DevgeiniError;
+use crate::stacks::builtin::BUILTIN_STACKS;
+use crate::stacks::plugin::register_plugins;
+use crate::stacks::{StackGenerator, StackKind};
+
+/// The stacks to offer in menus and to resolve ids against, in menu order.
+///
+/// `with_plugins` fills it with devgeini's built-in stacks and those of the installed plugins.
+/// Library users can also register their own `StackGenerator`s and pass the stacks that `frontend` and `backend`
+/// return in a `ProjectConfig` to `generate`.
+pub struct StackRegistry {
+    stacks: Vec<Arc<dyn StackGenerator>>,
+    warnings: Vec<String>,
+}
+
+impl StackRegistry {
+    /// A registry without any stacks. Built-in ids still resolve, as they need no registry.
+    pub fn new() -> Self {
+        StackRegistry {
+            stacks: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn with_builtins() -> Self {
+        let mut registry = StackRegistry::new();
+        for stack in BUILTIN_STACKS {
+            registry
+                .register(Box::new(*stack))
+                .expect("built-in stack ids are unique");
+        }
+        registry
+    }
+
+    /// The built-in stacks followed by those of the installed plugins.
+    ///
+    /// This runs every `devgeini-plugin-*` executable to ask for its stacks, so only load the
+    /// plugins when they are needed, e.g. for a stack id that is not built in.
+    pub fn with_plugins() -> Self {
+        let mut registry = StackRegistry::with_builtins();
+        registry.warnings = register_plugins(&mut registry);
+        registry
+    }
+
+    /// Adds `stack` after the ones already registered. Ids are unique per kind.
+    pub fn register(&mut self, stack: Box<dyn StackGenerator>) -> Result<(), DevgeiniError> {
+        if self.get(stack.kind(), stack.id()).is_some() {
+            return Err(DevgeiniError::InvalidConfig(format!(
+                "a {} stack called '{}' is already registered",
+                stack.kind().dir(),
+                stack.id()
+            )));
+        }
+        self.stacks.push(Arc::from(stack));
+        Ok(())
+    }
+
+    /// Why plugins or some of their stacks were left out.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    pub fn get(&self, kind: StackKind, id: &str) -> Option<&dyn StackGenerator> {
+        self.of_kind(kind).find(|stack| stack.id() == id)
+    }
+
+    /// Stacks of one kind; the first one is the default menu choice.
+    pub fn of_kind(&self, kind: StackKind) -> impl Iterator<Item = &dyn StackGenerator> {
+        self.stacks
+            .iter()
+            .map(|stack| stack.as_ref())
+            .filter(move |stack| stack.kind() == kind)
+    }
+
+    /// The built-in frontend stack called `id`, or else the registered one.
+    pub fn frontend(&self, id: &str) -> Result<FrontendStack, DevgeiniError> {
+        id.parse().or_else(|e| self.find(StackKind::Frontend, id).map(FrontendStack::Plugin).ok_or(e))
+    }
+
+    /// The built-in backend stack called `id`, or else the registered one.
+    pub fn backend(&self, id: &str) -> Result<BackendStack, DevgeiniError> {
+        id.parse().or_else(|e| self.find(StackKind::Backend, id).map(BackendStack::Plugin).ok_or(e))
+    }
+
+    fn find(&self, kind: StackKind, id: &str) -> Option<Arc<dyn StackGenerator>> {
+        self.stacks
+            .iter()
+            .find(|stack| stack.kind() == kind && stack.id() == id)
+            .cloned()
+    }
+}
+
+impl Default for StackRegistry {
+    fn default() -> Self {
+        StackRegistry::new()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::structure::{ProjectConfig, ProjectType};
+    use crate::stacks::{EnvVar, NextStep};
+    use crate::utils::writer::ProjectWriter;
+
+    struct TestStack {
+        id: &'static str,
+        kind: StackKind,
+    }
+
+    impl StackGenerator for TestStack {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn kind(&self) -> StackKind {
+            self.kind
+        }
+
+        fn display_name(&self) -> &str {
+            "Test"
+        }
+
+        fn description(&self) -> &str {
+            ""
+        }
+
+        fn language(&self) -> &str {
+            ""
+        }
+
+        fn write_files(&self, _: &ProjectConfig, writer: &mut dyn ProjectWriter) -> Result<(), DevgeiniError> {
+            writer.write("test.txt", self.id)
+        }
+
+        fn gitignore(&self) -> &str {
+            ""
+        }
+
+        fn write_dockerfile(&self, _: &ProjectConfig, _: &mut dyn ProjectWriter) -> Result<(), DevgeiniError> {
+            Ok(())
+        }
+
+        fn env_vars(&self, _: &ProjectConfig) -> Result<Vec<EnvVar>, DevgeiniError> {
+            Ok(Vec::new())
+        }
+
+        fn next_steps(&self) -> &[NextStep] {
+            &[]
+        }
+
+        fn dev_port(&self) -> u16 {
+            8000
+        }
+    }
+
+    fn test_stack(id: &'static str, kind: StackKind) -> Box<dyn StackGenerator> {
+        Box::new(TestStack { id, kind })
+    }
+
+    #[test]
+    fn builtins_keep_the_table_order() {
+        let registry = StackRegistry::with_builtins();
+        let frontends: Vec<&str> = registry.of_kind(StackKind::Frontend).map(|stack| stack.id()).collect();
+        let backends: Vec<&str> = registry.of_kind(StackKind::Backend).map(|stack| stack.id()).collect();
+
+        assert_eq!(frontends[0], "react-ts");
+        assert_eq!(backends[0], "node-ts");
+        assert_eq!(frontends.len() + backends.len(), BUILTIN_STACKS.len());
+        // Every built-in variant has its stack, and every built-in stack its variant
+        let variants: Vec<&str> = FrontendStack::BUILTIN.iter().map(FrontendStack::id).collect();
+        assert_eq!(variants, frontends);
+        let variants: Vec<&str> = BackendStack::BUILTIN.iter().map(BackendStack::id).collect();
+        assert_eq!(variants, backends);
+    }
+
+    #[test]
+    fn ids_are_unique_per_kind() {
+        let mut registry = StackRegistry::with_builtins();
+        let duplicate = registry.register(test_stack("go", StackKind::Backend));
+        assert!(matches!(duplicate, Err(DevgeiniError::InvalidConfig(_))), "{:?}", duplicate);
+
+        // The same id may name a stack of the other kind
+        registry.register(test_stack("go", StackKind::Frontend)).unwrap();
+        assert_eq!(registry.of_kind(StackKind::Frontend).last().unwrap().id(), "go");
+        assert_eq!(registry.of_kind(StackKind::Backend).filter(|stack| stack.id() == "go").count(), 1);
+    }
+
+    #[test]
+    fn registered_stacks_resolve_as_plugins() {
+        let mut registry = StackRegistry::new();
+        registry.register(test_stack("elm", StackKind::Frontend)).unwrap();
+
+        let elm = registry.frontend("elm").unwrap();
+        assert!(matches!(elm, FrontendStack::Plugin(_)));
+        assert_eq!(elm.generator().dev_port(), 8000);
+        // Built-in ids need no registration, other ids are unknown until registered
+        assert_eq!(registry.frontend("vue").unwrap(), FrontendStack::Vue);
+        assert!(matches!(
+            registry.backend("elm"),
+            Err(DevgeiniError::UnknownStack { kind: StackKind::Backend, .. })
+        ));
+
+        let mut config = ProjectConfig::new("shop".to_string(), ProjectType::Frontend);
+        config.frontend_stack = Some(elm);
+        let mut writer = crate::MemoryWriter::new();
+        crate::generate(&config, &mut writer).unwrap();
+        assert!(writer.file_names().contains(&"test.txt".to_string()));
+    }
+}
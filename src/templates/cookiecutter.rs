@@ -0,0 +1,265 @@
+use minijinja::{AutoEscape, Environment, Error, ErrorKind, State, UndefinedBehavior, Value};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::DevgeiniError;
+use crate::templates::external::{FileRules, Prompt, TemplateManifest};
+
+/// The variables file at the root of every cookiecutter template.
+pub const COOKIECUTTER_FILE: &str = "cookiecutter.json";
+
+// Human-readable questions keyed by variable name (cookiecutter 2.2+)
+const PROMPTS_KEY: &str = "__prompts__";
+const COPY_WITHOUT_RENDER_KEY: &str = "_copy_without_render";
+
+/// A `cookiecutter.json` expressed as a devgeini manifest, plus the variables that are
+/// passed to templates as they are instead of being asked for.
+pub struct CookiecutterTemplate {
+    pub manifest: TemplateManifest,
+    pub fixed: BTreeMap<String, JsonValue>,
+}
+
+/// Reads `cookiecutter.json` from `dir`, keeping the file's key order for the prompts.
+///
+/// Lists become menus whose first entry is the default, booleans become yes/no questions,
+/// `__` variables are rendered from earlier answers without asking and `_` variables
+/// (and dictionaries) are handed to templates unchanged.
+pub fn load(dir: &Path, source: &str) -> Result<CookiecutterTemplate, DevgeiniError> {
+    let path = dir.join(COOKIECUTTER_FILE);
+    let contents = fs::read_to_string(&path).map_err(|e| DevgeiniError::io(&path, e))?;
+    let variables: serde_json::Map<String, JsonValue> = serde_json::from_str(&contents)
+        .map_err(|e| DevgeiniError::Template(format!("could not parse {} in {}: {}", COOKIECUTTER_FILE, source, e)))?;
+
+    let messages = variables.get(PROMPTS_KEY).and_then(JsonValue::as_object);
+    let mut prompts = Vec::new();
+    let mut fixed = BTreeMap::new();
+
+    for (key, value) in &variables {
+        if key == PROMPTS_KEY {
+            continue;
+        }
+        let private = key.starts_with('_') && !key.starts_with("__");
+
+        let (default, choices) = match value {
+            _ if private => {
+                fixed.insert(key.clone(), value.clone());
+                continue;
+            }
+            JsonValue::String(text) => (Some(toml::Value::String(text.clone())), Vec::new()),
+            JsonValue::Bool(flag) => (Some(toml::Value::Boolean(*flag)), Vec::new()),
+            JsonValue::Number(number) => (Some(toml::Value::String(number.to_string())), Vec::new()),
+            JsonValue::Array(items) => {
+                let choices = items
+                    .iter()
+                    .map(|item| match item {
+                        JsonValue::String(text) => text.clone(),
+                        other => other.to_string(),
+                    })
+                    .collect();
+                (None, choices)
+            }
+            JsonValue::Object(_) | JsonValue::Null => {
+                fixed.insert(key.clone(), value.clone());
+                continue;
+            }
+        };
+
+        let message = messages.and_then(|messages| match messages.get(key) {
+            Some(JsonValue::String(message)) => Some(message.clone()),
+            // Choice variables describe the question under "__prompt__" and label each option
+            Some(JsonValue::Object(labels)) => labels.get("__prompt__").and_then(JsonValue::as_str).map(str::to_string),
+            _ => None,
+        });
+
+        prompts.push(Prompt {
+            key: key.clone(),
+            message,
+            default,
+            choices,
+            hidden: key.starts_with("__"),
+        });
+    }
+
+    let copy = match fixed.get(COPY_WITHOUT_RENDER_KEY) {
+        Some(JsonValue::Array(patterns)) => patterns
+            .iter()
+            .filter_map(JsonValue::as_str)
+            .map(str::to_string)
+            .collect(),
+        Some(_) => {
+            return Err(DevgeiniError::Template(format!(
+                "{} in {} must be a list of globs",
+                COPY_WITHOUT_RENDER_KEY,
+                path.display()
+            )))
+        }
+        None => Vec::new(),
+    };
+
+    Ok(CookiecutterTemplate {
+        manifest: TemplateManifest {
+            name: template_name(source),
+            description: String::new(),
+            language: String::new(),
+            project_types: Vec::new(),
+            prompts,
+            files: FileRules {
+                root: project_dir(dir)?,
+                copy,
+                exclude: Vec::new(),
+            },
+        },
+        fixed,
+    })
+}
+
+/// An environment that behaves like cookiecutter's Jinja2 setup: no block trimming,
+/// undefined variables are errors and common Python string methods work.
+pub fn environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    env.set_keep_trailing_newline(true);
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    env.set_auto_escape_callback(|_| AutoEscape::None);
+    env.set_unknown_method_callback(python_str_method);
+    env
+}
+
+// The project files live in the one top-level directory named after a variable,
+// e.g. `{{cookiecutter.project_slug}}`
+fn project_dir(dir: &Path) -> Result<String, DevgeiniError> {
+    let mut candidates: Vec<String> = fs::read_dir(dir)
+        .map_err(|e| DevgeiniError::io(dir, e))?
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.contains("{{") && name.contains("cookiecutter"))
+        .collect();
+
+    match candidates.len() {
+        1 => Ok(candidates.remove(0)),
+        0 => Err(DevgeiniError::Template(format!(
+            "cookiecutter template {} has no '{{{{cookiecutter.…}}}}' project directory",
+            dir.display()
+        ))),
+        _ => Err(DevgeiniError::Template(format!(
+            "cookiecutter template {} has several project directories: {}",
+            dir.display(),
+            candidates.join(", ")
+        ))),
+    }
+}
+
+// The repository or directory name, e.g. `cookiecutter-pypackage` for `gh:org/cookiecutter-pypackage.git`
+fn template_name(source: &str) -> String {
+    let trimmed = source.trim_end_matches(['/', '\\']);
+    let last = trimmed.rsplit(['/', '\\', ':']).next().unwrap_or(trimmed);
+    last.trim_end_matches(".git").to_string()
+}
+
+// The str methods cookiecutter templates lean on, e.g. `cookiecutter.project_name.lower().replace(' ', '_')`
+fn python_str_method(_state: &State, value: &Value, method: &str, args: &[Value]) -> Result<Value, Error> {
+    let Some(text) = value.as_str() else {
+        return Err(Error::from(ErrorKind::UnknownMethod));
+    };
+    let arg = |index: usize| {
+        args.get(index)
+            .and_then(|arg| arg.as_str())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidOperation, format!("{}() expects string arguments", method)))
+    };
+
+    let result = match (method, args.len()) {
+        ("lower", 0) => Value::from(text.to_lowercase()),
+        ("upper", 0) => Value::from(text.to_uppercase()),
+        ("strip", 0) => Value::from(text.trim()),
+        ("lstrip", 0) => Value::from(text.trim_start()),
+        ("rstrip", 0) => Value::from(text.trim_end()),
+        ("capitalize", 0) => {
+            let mut chars = text.chars();
+            Value::from(match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str().to_lowercase().as_str(),
+                None => String::new(),
+            })
+        }
+        ("title", 0) => {
+            let mut titled = String::with_capacity(text.len());
+            let mut start_of_word = true;
+            for c in text.chars() {
+                if start_of_word {
+                    titled.extend(c.to_uppercase());
+                } else {
+                    titled.extend(c.to_lowercase());
+                }
+                start_of_word = !c.is_alphanumeric();
+            }
+            Value::from(titled)
+        }
+        ("replace", 2) => Value::from(text.replace(arg(0)?, arg(1)?)),
+        ("startswith", 1) => Value::from(text.starts_with(arg(0)?)),
+        ("endswith", 1) => Value::from(text.ends_with(arg(0)?)),
+        ("split", 0) => Value::from(text.split_whitespace().map(Value::from).collect::<Vec<_>>()),
+        ("split", 1) => Value::from(text.split(arg(0)?).map(Value::from).collect::<Vec<_>>()),
+        _ => return Err(Error::from(ErrorKind::UnknownMethod)),
+    };
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use minijinja::context;
+
+    #[test]
+    fn loads_prompts_and_fixed_variables() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(COOKIECUTTER_FILE),
+            r#"{
+                "project_name": "My Package",
+                "license": ["MIT", "BSD-3-Clause"],
+                "use_docker": true,
+                "__slug": "{{ cookiecutter.project_name.lower().replace(' ', '_') }}",
+                "_copy_without_render": ["*.html", "assets"],
+                "__prompts__": {"project_name": "What is the project called?"}
+            }"#,
+        )
+        .unwrap();
+        fs::create_dir_all(dir.path().join("{{cookiecutter.__slug}}")).unwrap();
+        // Only the directory named after a variable holds the project
+        fs::create_dir_all(dir.path().join("hooks")).unwrap();
+
+        let template = load(dir.path(), "gh:org/cookiecutter-package.git").unwrap();
+        let manifest = template.manifest;
+        let keys: Vec<&str> = manifest.prompts.iter().map(|prompt| prompt.key.as_str()).collect();
+        assert_eq!(keys, ["project_name", "license", "use_docker", "__slug"]);
+        assert_eq!(manifest.name, "cookiecutter-package");
+
+        let [project_name, license, use_docker, slug] = &manifest.prompts[..] else {
+            unreachable!()
+        };
+        assert_eq!(project_name.text(), "What is the project called?");
+        assert_eq!(license.choices, ["MIT", "BSD-3-Clause"]);
+        assert_eq!(license.default, None);
+        assert_eq!(use_docker.default, Some(toml::Value::Boolean(true)));
+        assert!(slug.hidden && !project_name.hidden);
+
+        assert_eq!(manifest.files.copy, ["*.html", "assets"]);
+        assert!(template.fixed.contains_key(COPY_WITHOUT_RENDER_KEY));
+        assert_eq!(manifest.files.root, "{{cookiecutter.__slug}}");
+    }
+
+    #[test]
+    fn python_string_methods() {
+        let env = environment();
+        let render = |source: &str| env.render_str(source, context! { name => "  My Cool Package " }).unwrap();
+
+        assert_eq!(render("{{ name.strip().lower().replace(' ', '_') }}"), "my_cool_package");
+        assert_eq!(render("{{ name.strip().upper() }}"), "MY COOL PACKAGE");
+        assert_eq!(render("{{ 'hello world'.title() }} {{ 'hELLO'.capitalize() }}"), "Hello World Hello");
+        assert_eq!(render("{{ name.split()|join('-') }}"), "My-Cool-Package");
+        assert_eq!(render("{{ name.strip().startswith('My') }}"), "True");
+        assert!(env.render_str("{{ name.casefold() }}", context! { name => "x" }).is_err());
+    }
+}
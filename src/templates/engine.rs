@@ -0,0 +1,243 @@
+use include_dir::{include_dir, Dir};
+use minijinja::{context, AutoEscape, Environment, ErrorKind, Value};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::config::structure::ProjectConfig;
+use crate::error::DevgeiniError;
+use crate::stacks::StackGenerator;
+use crate::utils::writer::ProjectWriter;
+
+// The built-in templates, compiled into the binary so devgeini works without any setup
+static EMBEDDED: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/templates");
+
+// Every template file carries this suffix, which is dropped from the generated file name
+const TEMPLATE_SUFFIX: &str = ".j2";
+
+/// Files in this directory replace the built-in template with the same relative path,
+/// e.g. `~/.config/devgeini/templates/frontend/react-ts/src/App.tsx.j2`.
+pub fn override_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config").join("devgeini").join("templates"))
+}
+
+/// An environment with the settings every devgeini template is written against.
+pub fn new_environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    env.set_keep_trailing_newline(true);
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    // Generated files are source code, so nothing should be HTML-escaped
+    env.set_auto_escape_callback(|_| AutoEscape::None);
+    env
+}
+
+fn environment() -> &'static Environment<'static> {
+    static ENVIRONMENT: OnceLock<Environment<'static>> = OnceLock::new();
+    ENVIRONMENT.get_or_init(|| {
+        let mut env = new_environment();
+        env.set_loader(load_template);
+        env
+    })
+}
+
+fn load_template(name: &str) -> Result<Option<String>, minijinja::Error> {
+    load_template_from(override_dir().as_deref(), name)
+}
+
+fn load_template_from(override_root: Option<&Path>, name: &str) -> Result<Option<String>, minijinja::Error> {
+    if let Some(dir) = override_root {
+        let path = dir.join(name);
+        if path.is_file() {
+            return fs::read_to_string(&path).map(Some).map_err(|e| {
+                minijinja::Error::new(ErrorKind::InvalidOperation, format!("could not read {}", path.display()))
+                    .with_source(e)
+            });
+        }
+    }
+
+    Ok(EMBEDDED
+        .get_file(name)
+        .and_then(|file| file.contents_utf8())
+        .map(str::to_string))
+}
+
+/// The variables every template can use, including those set by hooks.
+pub fn template_context(config: &ProjectConfig) -> Value {
+    let builtin = context! {
+        name => &config.name,
+        project_type => config.project_type,
+        frontend_stack => &config.frontend_stack,
+        backend_stack => &config.backend_stack,
+        frontend => config.frontend_stack.as_ref().map(|stack| stack_context(stack.generator())),
+        backend => config.backend_stack.as_ref().map(|stack| stack_context(stack.generator())),
+        // Where the frontend reaches the API, also for frontend-only projects
+        api_port => config.backend_stack.as_ref().map_or(3001, |stack| stack.generator().dev_port()),
+        database => config.database.map(|database| context! {
+            id => database,
+            url => database.url("localhost", &config.identifiers.snake),
+            // The backend container reaches the database by its service name
+            compose_url => database.url("database", &config.identifiers.snake),
+        }),
+        identifiers => &config.identifiers,
+        package_dir => config.identifiers.package.replace('.', "/"),
+    };
+    context! { ..Value::from_serialize(&config.variables), ..builtin }
+}
+
+fn stack_context(stack: &dyn StackGenerator) -> Value {
+    context! {
+        id => stack.id(),
+        display_name => stack.display_name(),
+        description => stack.description(),
+        language => stack.language(),
+        next_steps => stack.next_steps(),
+        dev_port => stack.dev_port(),
+    }
+}
+
+/// Renders a single template, e.g. `common/README.md.j2`.
+pub fn render(name: &str, config: &ProjectConfig) -> Result<String, DevgeiniError> {
+    render_with(name, config, Value::UNDEFINED)
+}
+
+/// Like `render`, with the variables in `extra` added to the usual ones.
+pub fn render_with(name: &str, config: &ProjectConfig, extra: Value) -> Result<String, DevgeiniError> {
+    let template = environment().get_template(name).map_err(|e| render_error(name, e))?;
+    template
+        .render(context! { ..template_context(config), ..extra })
+        .map_err(|e| render_error(name, e))
+}
+
+/// Renders a template given as a string, such as an environment variable's value.
+pub fn render_str(source: &str, config: &ProjectConfig) -> Result<String, DevgeiniError> {
+    Ok(environment().render_str(source, template_context(config))?)
+}
+
+/// Renders every template below `dir` into `writer`, keeping the directory layout.
+///
+/// File names may contain variables too, such as `{{ package_dir }}` for Java sources.
+pub fn render_dir(dir: &str, config: &ProjectConfig, writer: &mut dyn ProjectWriter) -> Result<(), DevgeiniError> {
+    let env = environment();
+    let ctx = template_context(config);
+
+    for name in template_names(dir) {
+        let relative = name[dir.len() + 1..].strip_suffix(TEMPLATE_SUFFIX).unwrap_or_default();
+        let relative = if relative.contains("{{") {
+            env.render_named_str(&name, relative, &ctx).map_err(|e| render_error(&name, e))?
+        } else {
+            relative.to_string()
+        };
+
+        let contents = env
+            .get_template(&name)
+            .and_then(|template| template.render(&ctx))
+            .map_err(|e| render_error(&name, e))?;
+        writer.write(relative, contents)?;
+    }
+
+    Ok(())
+}
+
+// Point at the user's override when there is one, that is the file that needs fixing
+fn render_error(name: &str, source: minijinja::Error) -> DevgeiniError {
+    let template = override_dir()
+        .map(|dir| dir.join(name))
+        .filter(|path| path.is_file())
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| name.to_string());
+    DevgeiniError::TemplateRender { template, source }
+}
+
+// Built-in and user-provided template names below `dir`, so overrides can also add new files
+fn template_names(dir: &str) -> BTreeSet<String> {
+    template_names_from(override_dir().as_deref(), dir)
+}
+
+fn template_names_from(override_root: Option<&Path>, dir: &str) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+
+    if let Some(embedded) = EMBEDDED.get_dir(dir) {
+        collect_embedded(embedded, &mut names);
+    }
+    if let Some(override_root) = override_root {
+        collect_overrides(override_root, &override_root.join(dir), &mut names);
+    }
+
+    names.retain(|name| name.ends_with(TEMPLATE_SUFFIX));
+    names
+}
+
+fn collect_embedded(dir: &Dir<'static>, names: &mut BTreeSet<String>) {
+    for file in dir.files() {
+        names.insert(template_name(file.path()));
+    }
+    for child in dir.dirs() {
+        collect_embedded(child, names);
+    }
+}
+
+fn collect_overrides(root: &Path, dir: &Path, names: &mut BTreeSet<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_overrides(root, &path, names);
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            names.insert(template_name(relative));
+        }
+    }
+}
+
+// Template names always use forward slashes, whatever the host OS
+fn template_name(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_override(root: &Path, name: &str, contents: &str) {
+        let path = root.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn overrides_replace_built_in_templates() {
+        let overrides = tempfile::tempdir().unwrap();
+        write_override(overrides.path(), "common/README.md.j2", "# {{ name }} (ours)\n");
+
+        let readme = load_template_from(Some(overrides.path()), "common/README.md.j2").unwrap();
+        assert_eq!(readme.as_deref(), Some("# {{ name }} (ours)\n"));
+
+        // Everything not overridden still comes from the built-in templates
+        let built_in = load_template_from(Some(overrides.path()), "common/.gitignore.j2").unwrap();
+        assert_eq!(built_in, load_template_from(None, "common/.gitignore.j2").unwrap());
+        assert!(built_in.is_some());
+    }
+
+    #[test]
+    fn overrides_can_add_files_to_a_stack() {
+        let overrides = tempfile::tempdir().unwrap();
+        write_override(overrides.path(), "backend/go/Makefile.j2", "run:\n\tgo run ./cmd/server\n");
+        write_override(overrides.path(), "backend/go/notes.txt", "not a template\n");
+
+        let built_in = template_names_from(None, "backend/go");
+        let names = template_names_from(Some(overrides.path()), "backend/go");
+
+        assert!(names.contains("backend/go/Makefile.j2"));
+        assert!(!names.contains("backend/go/notes.txt"));
+        assert!(names.is_superset(&built_in));
+        assert_eq!(names.len(), built_in.len() + 1);
+    }
+}
+
@@ -0,0 +1,680 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use minijinja::{Environment, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::naming::ProjectIdentifiers;
+use crate::config::structure::ProjectType;
+use crate::error::DevgeiniError;
+use crate::templates::cookiecutter::{self, COOKIECUTTER_FILE};
+use crate::templates::engine::new_environment;
+use crate::templates::hooks::{HookConfig, Hooks, HOOKS_DIR};
+use crate::utils::writer::{is_inside_project, ProjectWriter};
+
+/// Describes a project template; lives at the root of the template repository.
+pub const MANIFEST_FILE: &str = "devgeini-template.toml";
+/// Written into every project generated from a template.
+pub const RECORD_FILE: &str = ".devgeini-template.toml";
+
+/// Templates in this directory can be used by name, e.g. `init --template acme-service`
+/// for `~/.config/devgeini/project-templates/acme-service`.
+pub fn installed_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config").join("devgeini").join("project-templates"))
+}
+
+// Variables devgeini always provides, so prompts cannot reuse these keys
+const RESERVED_KEYS: &[&str] = &["name", "identifiers"];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateManifest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Shown by `devgeini list` and matched by `devgeini search`
+    #[serde(default)]
+    pub language: String,
+    #[serde(default)]
+    pub project_types: Vec<ProjectType>,
+    #[serde(default, rename = "prompt")]
+    pub prompts: Vec<Prompt>,
+    #[serde(default)]
+    pub files: FileRules,
+}
+
+/// A question asked before rendering. Choices make it a menu and a boolean default a yes/no question.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Prompt {
+    pub key: String,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub default: Option<toml::Value>,
+    #[serde(default)]
+    pub choices: Vec<String>,
+    /// Computed from earlier answers instead of being asked, unless set with `--set`
+    #[serde(skip)]
+    pub hidden: bool,
+}
+
+impl Prompt {
+    /// The question shown for this prompt, its key unless the template sets a message.
+    pub fn text(&self) -> &str {
+        self.message.as_deref().unwrap_or(&self.key)
+    }
+}
+
+/// Asks the user one prompt, given its default rendered from the earlier answers.
+pub type AskPrompt<'a> = &'a dyn Fn(&Prompt, Option<toml::Value>) -> Result<toml::Value, DevgeiniError>;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileRules {
+    /// Directory holding the project files, relative to the manifest
+    #[serde(default = "default_files_root")]
+    pub root: String,
+    /// Globs for files that are copied without rendering, such as images
+    #[serde(default)]
+    pub copy: Vec<String>,
+    /// Globs for files that are never generated
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl Default for FileRules {
+    fn default() -> Self {
+        FileRules {
+            root: default_files_root(),
+            copy: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+}
+
+fn default_files_root() -> String {
+    "template".to_string()
+}
+
+/// Where a generated project came from, saved as `RECORD_FILE`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateRecord {
+    pub template: String,
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    pub answers: BTreeMap<String, toml::Value>,
+}
+
+/// How a template lays out its variables.
+pub enum TemplateFormat {
+    /// A `devgeini-template.toml` manifest; answers are top-level variables
+    Devgeini,
+    /// A `cookiecutter.json`; answers and the fixed values live under `cookiecutter.`
+    Cookiecutter(BTreeMap<String, serde_json::Value>),
+}
+
+/// A template checked out on disk, cloned into a temporary directory when it came from git.
+pub struct ExternalTemplate {
+    pub manifest: TemplateManifest,
+    pub format: TemplateFormat,
+    pub source: String,
+    pub commit: Option<String>,
+    /// Problems with the template that do not stop it from being used
+    pub warnings: Vec<String>,
+    hooks: Hooks,
+    dir: PathBuf,
+    is_clone: bool,
+}
+
+impl ExternalTemplate {
+    /// Uses a local directory as-is, anything else (URLs, `file://`, bare repositories) is cloned with git.
+    pub fn fetch(source: &str) -> Result<Self, DevgeiniError> {
+        let (dir, is_clone) = match local_template(source) {
+            Some(dir) => (dir, false),
+            None if is_plain_dir(source) => {
+                return Err(DevgeiniError::Template(format!(
+                    "no template manifest in '{}' (expected {} or {})",
+                    source, MANIFEST_FILE, COOKIECUTTER_FILE
+                )))
+            }
+            None => (clone_template(source)?, true),
+        };
+
+        let loaded = load_template(&dir, source).and_then(|(manifest, format)| Ok((manifest, format, Hooks::load(&dir)?)));
+        let (manifest, format, hooks) = match loaded {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                if is_clone {
+                    let _ = fs::remove_dir_all(&dir);
+                }
+                return Err(e);
+            }
+        };
+
+        let mut warnings = Vec::new();
+        if matches!(format, TemplateFormat::Cookiecutter(_)) && has_foreign_hooks(&dir) {
+            warnings.push("this cookiecutter template has Python or shell hooks; devgeini only runs .rhai hooks".to_string());
+        }
+
+        Ok(ExternalTemplate {
+            manifest,
+            format,
+            source: source.to_string(),
+            commit: current_commit(&dir),
+            warnings,
+            hooks,
+            dir,
+            is_clone,
+        })
+    }
+
+    /// The temporary checkout of a template fetched with git, removed again when it is dropped.
+    pub fn clone_dir(&self) -> Option<&Path> {
+        self.is_clone.then_some(self.dir.as_path())
+    }
+
+    /// Answers every prompt, taking `preset` values first. The others go to `ask_prompt`,
+    /// or get their defaults when it is `None`, e.g. because there is no terminal to ask on.
+    pub fn ask(
+        &self,
+        preset: &BTreeMap<String, String>,
+        name: &str,
+        ask_prompt: Option<AskPrompt>,
+    ) -> Result<BTreeMap<String, toml::Value>, DevgeiniError> {
+        let env = self.environment();
+        let mut answers = BTreeMap::new();
+
+        for prompt in &self.manifest.prompts {
+            // String defaults may refer to the project name and earlier answers, e.g. "{{ name }}-service"
+            let default = match &prompt.default {
+                Some(default) => Some(render_default(prompt, default, &env, &self.context(name, &answers))?),
+                None => None,
+            };
+            let answer = match preset.get(&prompt.key) {
+                Some(value) => parse_answer(prompt, value)?,
+                None => match ask_prompt {
+                    Some(ask_prompt) if !prompt.hidden => ask_prompt(prompt, default)?,
+                    _ => default_answer(prompt, default)?,
+                },
+            };
+            answers.insert(prompt.key.clone(), answer);
+        }
+
+        if let Some(unknown) = preset.keys().find(|key| !answers.contains_key(*key)) {
+            return Err(DevgeiniError::InvalidConfig(format!(
+                "template '{}' has no prompt called '{}'",
+                self.manifest.name, unknown
+            )));
+        }
+
+        Ok(answers)
+    }
+
+    /// Renders the template's files into `writer`, between its pre- and post-generation hooks,
+    /// and records where they came from. Returns the lines the hooks printed.
+    pub fn render(
+        &self,
+        name: &str,
+        answers: &BTreeMap<String, toml::Value>,
+        writer: &mut dyn ProjectWriter,
+    ) -> Result<Vec<String>, DevgeiniError> {
+        let root = self.dir.join(&self.manifest.files.root);
+        if !root.is_dir() {
+            return Err(DevgeiniError::Template(format!(
+                "template files directory '{}' does not exist (set [files] root in {})",
+                root.display(),
+                MANIFEST_FILE
+            )));
+        }
+
+        // Hooks may add variables, the record keeps the answers that can be replayed with --set
+        let hook_output = self.hooks.run(&HookConfig::from_name(name), answers, answers.clone(), writer, |variables, writer| {
+            render_tree(
+                &root,
+                &self.environment(),
+                &self.context(name, variables),
+                &self.manifest.files.copy,
+                &self.manifest.files.exclude,
+                writer,
+            )
+        })?;
+
+        let record = TemplateRecord {
+            template: self.manifest.name.clone(),
+            source: self.source.clone(),
+            commit: self.commit.clone(),
+            answers: answers.clone(),
+        };
+        let mut record_content = String::from("# Generated by devgeini from a project template\n");
+        let record_toml = toml::to_string_pretty(&record)
+            .map_err(|e| DevgeiniError::Template(format!("could not write {}: {}", RECORD_FILE, e)))?;
+        record_content.push_str(&record_toml);
+        writer.write(RECORD_FILE, record_content)?;
+
+        Ok(hook_output)
+    }
+
+    fn environment(&self) -> Environment<'static> {
+        match self.format {
+            TemplateFormat::Devgeini => new_environment(),
+            TemplateFormat::Cookiecutter(_) => cookiecutter::environment(),
+        }
+    }
+
+    fn context(&self, name: &str, answers: &BTreeMap<String, toml::Value>) -> Value {
+        let mut ctx = base_context(name);
+        match &self.format {
+            TemplateFormat::Devgeini => {
+                for (key, value) in answers {
+                    ctx.insert(key.clone(), Value::from_serialize(value));
+                }
+            }
+            TemplateFormat::Cookiecutter(fixed) => {
+                let mut variables: BTreeMap<String, Value> = fixed
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Value::from_serialize(value)))
+                    .collect();
+                for (key, value) in answers {
+                    variables.insert(key.clone(), Value::from_serialize(value));
+                }
+                ctx.insert("cookiecutter".to_string(), Value::from(variables));
+            }
+        }
+        Value::from(ctx)
+    }
+}
+
+impl Drop for ExternalTemplate {
+    fn drop(&mut self) {
+        if self.is_clone {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+/// Reads the manifest of the template checked out in `dir`, whichever format it uses.
+pub fn load_template(dir: &Path, source: &str) -> Result<(TemplateManifest, TemplateFormat), DevgeiniError> {
+    if dir.join(MANIFEST_FILE).is_file() || !dir.join(COOKIECUTTER_FILE).is_file() {
+        return Ok((load_manifest(dir, source)?, TemplateFormat::Devgeini));
+    }
+    let template = cookiecutter::load(dir, source)?;
+    Ok((template.manifest, TemplateFormat::Cookiecutter(template.fixed)))
+}
+
+// Cookiecutter's own pre_gen_project.py / post_gen_project.sh hooks
+fn has_foreign_hooks(dir: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(dir.join(HOOKS_DIR)) else {
+        return false;
+    };
+    entries
+        .flatten()
+        .any(|entry| entry.path().extension().is_none_or(|extension| extension != "rhai"))
+}
+
+/// Whether `fetch` has to clone `source` with git, rather than using a directory on disk.
+pub fn is_remote(source: &str) -> bool {
+    local_template(source).is_none() && !is_plain_dir(source)
+}
+
+// A directory git cannot clone from, so a missing manifest is reported instead of a failed clone
+fn is_plain_dir(source: &str) -> bool {
+    let dir = Path::new(source);
+    let is_bare_repository = dir.join("HEAD").is_file() && dir.join("objects").is_dir();
+    dir.is_dir() && !is_bare_repository
+}
+
+fn local_template(source: &str) -> Option<PathBuf> {
+    let local = installed_template(source).unwrap_or_else(|| PathBuf::from(source));
+    is_template_dir(&local).then_some(local)
+}
+
+pub fn is_template_dir(dir: &Path) -> bool {
+    dir.join(MANIFEST_FILE).is_file() || dir.join(COOKIECUTTER_FILE).is_file()
+}
+
+// Plain names that are not a path in the current directory refer to installed templates
+fn installed_template(source: &str) -> Option<PathBuf> {
+    if source.contains(['/', '\\', ':']) || Path::new(source).exists() {
+        return None;
+    }
+    let dir = installed_dir()?.join(source);
+    is_template_dir(&dir).then_some(dir)
+}
+
+fn load_manifest(dir: &Path, source: &str) -> Result<TemplateManifest, DevgeiniError> {
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let contents = fs::read_to_string(&manifest_path).map_err(|_| {
+        DevgeiniError::Template(format!("'{}' is not a devgeini template (no {} found)", source, MANIFEST_FILE))
+    })?;
+    let manifest: TemplateManifest = toml::from_str(&contents)
+        .map_err(|e| DevgeiniError::Template(format!("could not parse {}: {}", manifest_path.display(), e)))?;
+
+    if let Some(prompt) = manifest.prompts.iter().find(|p| RESERVED_KEYS.contains(&p.key.as_str())) {
+        return Err(DevgeiniError::Template(format!(
+            "prompt key '{}' in {} is reserved by devgeini",
+            prompt.key,
+            manifest_path.display()
+        )));
+    }
+
+    Ok(manifest)
+}
+
+fn clone_template(source: &str) -> Result<PathBuf, DevgeiniError> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    let target = std::env::temp_dir().join(format!("devgeini-template-{}-{}", std::process::id(), nanos));
+
+    let mut git = Command::new("git");
+    git.args(["clone", "--quiet"]);
+    // Shallow clones only work over a transport, git ignores --depth for plain local paths
+    if !Path::new(source).exists() {
+        git.args(["--depth", "1"]);
+    }
+    // `--` keeps a source starting with '-' from being read as an option
+    let status = git
+        .arg("--")
+        .arg(source)
+        .arg(&target)
+        .status()
+        .map_err(|e| DevgeiniError::Template(format!("git is required to fetch '{}': {}", source, e)))?;
+
+    if !status.success() {
+        let _ = fs::remove_dir_all(&target);
+        return Err(DevgeiniError::Template(format!(
+            "could not clone template '{}' (check the URL and that you have access)",
+            source
+        )));
+    }
+
+    Ok(target)
+}
+
+// None for templates that are plain directories rather than git checkouts. A directory inside
+// some other repository is not a checkout of its own, so that repository's HEAD is not recorded.
+fn current_commit(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["rev-parse", "--show-toplevel", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let toplevel = fs::canonicalize(lines.next()?.trim()).ok()?;
+    if toplevel != fs::canonicalize(dir).ok()? {
+        return None;
+    }
+    lines.next().map(|commit| commit.trim().to_string())
+}
+
+fn parse_answer(prompt: &Prompt, value: &str) -> Result<toml::Value, DevgeiniError> {
+    if !prompt.choices.is_empty() {
+        if !prompt.choices.iter().any(|choice| choice == value) {
+            return Err(DevgeiniError::InvalidConfig(format!(
+                "'{}' is not a valid answer for '{}' (choose from {})",
+                value,
+                prompt.key,
+                prompt.choices.join(", ")
+            )));
+        }
+        return Ok(toml::Value::String(value.to_string()));
+    }
+
+    if let Some(toml::Value::Boolean(_)) = prompt.default {
+        return match value {
+            "true" | "yes" | "y" => Ok(toml::Value::Boolean(true)),
+            "false" | "no" | "n" => Ok(toml::Value::Boolean(false)),
+            _ => Err(DevgeiniError::InvalidConfig(format!(
+                "'{}' expects true or false, got '{}'",
+                prompt.key, value
+            ))),
+        };
+    }
+
+    Ok(toml::Value::String(value.to_string()))
+}
+
+fn render_default(prompt: &Prompt, value: &toml::Value, env: &Environment, ctx: &Value) -> Result<toml::Value, DevgeiniError> {
+    match value {
+        toml::Value::String(text) if text.contains("{{") || text.contains("{%") => {
+            let rendered = env.render_named_str(&format!("default of '{}'", prompt.key), text, ctx)?;
+            Ok(toml::Value::String(rendered))
+        }
+        other => Ok(other.clone()),
+    }
+}
+
+fn default_answer(prompt: &Prompt, default: Option<toml::Value>) -> Result<toml::Value, DevgeiniError> {
+    match (default, prompt.choices.first()) {
+        (Some(default), _) => Ok(default),
+        (None, Some(first)) => Ok(toml::Value::String(first.clone())),
+        (None, None) => Err(DevgeiniError::InvalidConfig(format!(
+            "no answer for '{}'; pass --set {}=<value>",
+            prompt.key, prompt.key
+        ))),
+    }
+}
+
+fn base_context(name: &str) -> BTreeMap<String, Value> {
+    let mut ctx = BTreeMap::new();
+    ctx.insert("name".to_string(), Value::from(name));
+    ctx.insert("identifiers".to_string(), Value::from_serialize(ProjectIdentifiers::from_name(name)));
+    ctx
+}
+
+fn build_globs(patterns: &[String]) -> Result<GlobSet, DevgeiniError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| DevgeiniError::Template(format!("invalid glob in [files]: {}", e)))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| DevgeiniError::Template(format!("invalid glob in [files]: {}", e)))
+}
+
+/// Renders every file below `root` into `writer`, file names included.
+///
+/// A file whose name renders to an empty path segment is skipped, which lets templates
+/// make files optional with e.g. `{% if docker %}Dockerfile{% endif %}`. A `copy` glob that
+/// matches a directory keeps everything inside it unrendered.
+pub fn render_tree(
+    root: &Path,
+    env: &Environment,
+    ctx: &Value,
+    copy: &[String],
+    exclude: &[String],
+    writer: &mut dyn ProjectWriter,
+) -> Result<(), DevgeiniError> {
+    let copy = build_globs(copy)?;
+    let exclude = build_globs(exclude)?;
+
+    for relative in collect_template_files(root)? {
+        if exclude.is_match(&relative) {
+            continue;
+        }
+
+        // Rendered as a whole so a variable may expand to several segments, e.g. a Java package path
+        let rendered = env.render_named_str(&relative, &relative, ctx)?;
+        if rendered.split('/').any(|segment| segment.trim().is_empty()) {
+            continue;
+        }
+        let rendered_path: PathBuf = rendered.split('/').collect();
+        if !is_inside_project(&rendered_path) {
+            return Err(DevgeiniError::Template(format!(
+                "template file '{}' renders to '{}', which is outside the project",
+                relative, rendered
+            )));
+        }
+
+        let copy_only = Path::new(&relative).ancestors().any(|path| !path.as_os_str().is_empty() && copy.is_match(path));
+        let source_path = root.join(&relative);
+        let source = fs::read(&source_path).map_err(|e| DevgeiniError::io(&source_path, e))?;
+        match String::from_utf8(source) {
+            Ok(text) if !copy_only => {
+                let contents = env.render_named_str(&relative, &text, ctx)?;
+                writer.write(rendered_path, contents)?;
+            }
+            // Binary files and `copy` globs are written byte for byte
+            Ok(text) => writer.write(rendered_path, text)?,
+            Err(e) => writer.write(rendered_path, e.into_bytes())?,
+        }
+    }
+
+    Ok(())
+}
+
+// Relative, forward-slash paths of every file below `root`, leaving out git metadata
+fn collect_template_files(root: &Path) -> Result<Vec<String>, DevgeiniError> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir).map_err(|e| DevgeiniError::io(&dir, e))?;
+        for entry in entries {
+            let path = entry.map_err(|e| DevgeiniError::io(&dir, e))?.path();
+            if path.file_name().is_some_and(|name| name == ".git") {
+                continue;
+            }
+            if path.is_dir() {
+                pending.push(path);
+            } else if path != root.join(MANIFEST_FILE) {
+                let Ok(relative) = path.strip_prefix(root) else {
+                    continue;
+                };
+                files.push(
+                    relative
+                        .components()
+                        .map(|c| c.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                );
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::writer::MemoryWriter;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=devgeini", "-c", "user.email=devgeini@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn write_template(dir: &Path, files: &[(&str, &str)]) {
+        fs::write(
+            dir.join(MANIFEST_FILE),
+            "name = \"service\"\n\n[[prompt]]\nkey = \"docker\"\ndefault = false\n",
+        )
+        .unwrap();
+        for (path, contents) in files {
+            let path = dir.join("template").join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn clones_and_renders_a_bare_repository() {
+        let work = tempfile::tempdir().unwrap();
+        write_template(
+            work.path(),
+            &[
+                ("README.md", "# {{ name }}\n"),
+                ("src/{{ identifiers.snake }}.py", "print('{{ identifiers.pascal }}')\n"),
+                ("{% if docker %}Dockerfile{% endif %}", "FROM python:3.12\n"),
+            ],
+        );
+        git(work.path(), &["init", "--quiet"]);
+        git(work.path(), &["add", "."]);
+        git(work.path(), &["commit", "--quiet", "-m", "template"]);
+
+        let repos = tempfile::tempdir().unwrap();
+        let bare = repos.path().join("service.git");
+        let status = Command::new("git")
+            .args(["clone", "--quiet", "--bare"])
+            .arg(work.path())
+            .arg(&bare)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let template = ExternalTemplate::fetch(bare.to_str().unwrap()).unwrap();
+        assert!(template.commit.is_some());
+        let preset = BTreeMap::from([("docker".to_string(), "true".to_string())]);
+        let answers = template.ask(&preset, "my-service", None).unwrap();
+
+        let mut writer = MemoryWriter::new();
+        template.render("my-service", &answers, &mut writer).unwrap();
+        assert_eq!(
+            writer.file_names(),
+            [RECORD_FILE, "Dockerfile", "README.md", "src/my_service.py"]
+        );
+        let files: BTreeMap<_, _> = writer.files().collect();
+        assert_eq!(files[Path::new("README.md")], b"# my-service\n");
+        assert_eq!(files[Path::new("src/my_service.py")], b"print('MyService')\n");
+
+        // The temporary clone goes away with the template
+        let clone = template.dir.clone();
+        drop(template);
+        assert!(!clone.exists());
+    }
+
+    #[test]
+    fn records_no_commit_for_a_directory_inside_another_repository() {
+        let repo = tempfile::tempdir().unwrap();
+        git(repo.path(), &["init", "--quiet"]);
+        let dir = repo.path().join("templates").join("service");
+        fs::create_dir_all(&dir).unwrap();
+        write_template(&dir, &[("README.md", "# {{ name }}\n")]);
+        git(repo.path(), &["add", "."]);
+        git(repo.path(), &["commit", "--quiet", "-m", "templates"]);
+
+        let template = ExternalTemplate::fetch(dir.to_str().unwrap()).unwrap();
+        assert_eq!(template.commit, None);
+    }
+
+    #[test]
+    fn rejects_paths_that_render_outside_the_project() {
+        let dir = tempfile::tempdir().unwrap();
+        write_template(dir.path(), &[("{{ name }}/notes.txt", "escaped\n")]);
+
+        let template = ExternalTemplate::fetch(dir.path().to_str().unwrap()).unwrap();
+        let mut writer = MemoryWriter::new();
+        let result = template.render("..", &BTreeMap::new(), &mut writer);
+        assert!(matches!(result, Err(DevgeiniError::Template(_))), "{:?}", result.err());
+        assert_eq!(writer.files().count(), 0);
+    }
+
+    #[test]
+    fn directories_without_a_manifest_are_not_cloned() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("README.md"), "# not a template\n").unwrap();
+        let source = dir.path().to_str().unwrap();
+
+        assert!(!is_remote(source));
+        let error = ExternalTemplate::fetch(source).err().unwrap();
+        assert!(matches!(error, DevgeiniError::Template(_)), "{:?}", error);
+        assert!(error.to_string().contains("no template manifest"), "{}", error);
+    }
+}
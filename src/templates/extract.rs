@@ -0,0 +1,218 @@
+use globset::escape;
+use ignore::WalkBuilder;
+use std::fs;
+use std::path::Path;
+
+use crate::config::naming::ProjectIdentifiers;
+use crate::error::DevgeiniError;
+use crate::templates::external::{FileRules, TemplateManifest, MANIFEST_FILE, RECORD_FILE};
+use crate::utils::writer::ProjectWriter;
+
+// Dependency and build output, skipped even without a .gitignore saying so
+const ALWAYS_SKIPPED: &[&str] = &["node_modules", "target", ".git"];
+
+// Written out as expressions so template syntax already in the project survives rendering
+const JINJA_DELIMITERS: &[(&str, &str)] = &[
+    ("{{", "{{ \"{{\" }}"),
+    ("{%", "{{ \"{%\" }}"),
+    ("{#", "{{ \"{#\" }}"),
+];
+
+/// Turns the project in `source` into a template that `init --template` can use: a
+/// manifest plus a `template/` directory in which `project_name` and the identifiers
+/// derived from it are replaced with placeholders.
+///
+/// Ignored paths (`.gitignore`, `node_modules`, `target`, `.git`) are left out.
+pub fn extract_template(source: &Path, project_name: &str, writer: &mut dyn ProjectWriter) -> Result<(), DevgeiniError> {
+    let replacements = name_replacements(project_name);
+    let template_root = FileRules::default().root;
+    let mut copy = Vec::new();
+
+    let walker = WalkBuilder::new(source)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| !ALWAYS_SKIPPED.iter().any(|skipped| entry.file_name() == *skipped))
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+
+    for entry in walker {
+        // ignore's errors already name the file that could not be read
+        let entry = entry.map_err(|e| DevgeiniError::io(source, std::io::Error::other(e)))?;
+        if !entry.file_type().is_some_and(|file_type| file_type.is_file()) {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(source) else {
+            continue;
+        };
+        let relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        // A project generated from a template gets a fresh record (and manifest) on init
+        if relative == RECORD_FILE || relative == MANIFEST_FILE {
+            continue;
+        }
+
+        let (relative_template_path, _) = replace_names(&relative, &replacements);
+        let template_path = format!("{}/{}", template_root, relative_template_path);
+
+        let contents = fs::read(entry.path()).map_err(|e| DevgeiniError::io(entry.path(), e))?;
+        match String::from_utf8(contents) {
+            Ok(text) => {
+                let (template_text, replaced) = replace_names(&text, &replacements);
+                if replaced == 0 && template_text != text {
+                    // Nothing to fill in, so keep the file readable instead of escaping it
+                    copy.push(escape(&relative_template_path));
+                    writer.write(template_path, text)?;
+                } else {
+                    writer.write(template_path, template_text)?;
+                }
+            }
+            Err(e) => writer.write(template_path, e.into_bytes())?,
+        }
+    }
+
+    let manifest = TemplateManifest {
+        name: format!("{}-template", ProjectIdentifiers::from_name(project_name).kebab),
+        description: format!("Created from '{}' with devgeini template new", project_name),
+        language: String::new(),
+        project_types: Vec::new(),
+        prompts: Vec::new(),
+        files: FileRules {
+            copy,
+            ..FileRules::default()
+        },
+    };
+    let mut manifest_content = String::from("# Generated by devgeini. Add [[prompt]] tables to ask questions on init.\n");
+    let manifest_toml = toml::to_string_pretty(&manifest)
+        .map_err(|e| DevgeiniError::Template(format!("could not write {}: {}", MANIFEST_FILE, e)))?;
+    manifest_content.push_str(&manifest_toml);
+    writer.write(MANIFEST_FILE, manifest_content)?;
+
+    Ok(())
+}
+
+// Every spelling of the name with the variable that produces it, longest first so
+// `my-app-server` is not partly replaced by a shorter match
+fn name_replacements(project_name: &str) -> Vec<(String, &'static str)> {
+    let identifiers = ProjectIdentifiers::from_name(project_name);
+    let candidates = [
+        (project_name.to_string(), "{{ name }}"),
+        (identifiers.kebab, "{{ identifiers.kebab }}"),
+        (identifiers.snake, "{{ identifiers.snake }}"),
+        (identifiers.pascal, "{{ identifiers.pascal }}"),
+        (identifiers.package.clone(), "{{ identifiers.package }}"),
+        (identifiers.package.replace('.', "/"), "{{ identifiers.package | replace('.', '/') }}"),
+    ];
+
+    let mut replacements: Vec<(String, &'static str)> = Vec::new();
+    for (literal, placeholder) in candidates {
+        if !literal.is_empty() && !replacements.iter().any(|(existing, _)| *existing == literal) {
+            replacements.push((literal, placeholder));
+        }
+    }
+    replacements.sort_by_key(|(literal, _)| std::cmp::Reverse(literal.len()));
+    replacements
+}
+
+// Replaces whole-word occurrences of the name and escapes existing template syntax,
+// returning the template text and how many names were replaced
+fn replace_names(text: &str, replacements: &[(String, &'static str)]) -> (String, usize) {
+    let mut output = String::with_capacity(text.len());
+    let mut replaced = 0;
+    let mut rest = text;
+
+    'scan: while let Some(c) = rest.chars().next() {
+        for (delimiter, escaped) in JINJA_DELIMITERS {
+            if rest.starts_with(delimiter) {
+                output.push_str(escaped);
+                rest = &rest[delimiter.len()..];
+                continue 'scan;
+            }
+        }
+
+        let preceded_by_word = output.chars().next_back().is_some_and(continues_word);
+        if !preceded_by_word {
+            for (literal, placeholder) in replacements {
+                if rest.starts_with(literal.as_str()) && !rest[literal.len()..].chars().next().is_some_and(continues_word) {
+                    output.push_str(placeholder);
+                    rest = &rest[literal.len()..];
+                    replaced += 1;
+                    continue 'scan;
+                }
+            }
+        }
+
+        output.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    (output, replaced)
+}
+
+// `app` must not match inside `application` or `webapp`, but `MyApp` may in `MyAppTests`
+fn continues_word(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::templates::engine::new_environment;
+    use crate::utils::writer::MemoryWriter;
+    use minijinja::context;
+
+    #[test]
+    fn replaces_whole_words_only() {
+        let replacements = name_replacements("app");
+        let (text, replaced) = replace_names("app is an application, not a webapp", &replacements);
+        assert_eq!(text, "{{ name }} is an application, not a webapp");
+        assert_eq!(replaced, 1);
+    }
+
+    #[test]
+    fn pascal_case_name_matches_before_a_capital() {
+        let replacements = name_replacements("my-app");
+        let (text, replaced) = replace_names("class MyAppTests extends MyApplication {}", &replacements);
+        assert_eq!(text, "class {{ identifiers.pascal }}Tests extends MyApplication {}");
+        assert_eq!(replaced, 1);
+    }
+
+    #[test]
+    fn longest_spelling_wins() {
+        let replacements = name_replacements("shop");
+        let (text, _) = replace_names("package com.example.shop; // src/main/java/com/example/shop", &replacements);
+        assert_eq!(
+            text,
+            "package {{ identifiers.package }}; // src/main/java/{{ identifiers.package | replace('.', '/') }}"
+        );
+    }
+
+    #[test]
+    fn escapes_existing_template_syntax() {
+        let original = "<h1>{{ title }}</h1>{% if shop %}{# shop #}{% endif %}";
+        let (text, replaced) = replace_names(original, &name_replacements("shop"));
+        assert_eq!(replaced, 2);
+
+        let rendered = new_environment().render_str(&text, context! { name => "shop" }).unwrap();
+        assert_eq!(rendered, original);
+    }
+
+    #[test]
+    fn copies_files_with_template_syntax_but_no_name() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("README.md"), "# shop\n").unwrap();
+        fs::write(dir.path().join("layout.html"), "<title>{{ title }}</title>\n").unwrap();
+
+        let mut writer = MemoryWriter::new();
+        extract_template(dir.path(), "shop", &mut writer).unwrap();
+        let files: std::collections::BTreeMap<_, _> = writer.files().collect();
+
+        assert_eq!(files[Path::new("template/README.md")], b"# {{ name }}\n");
+        assert_eq!(files[Path::new("template/layout.html")], b"<title>{{ title }}</title>\n");
+        let manifest: TemplateManifest =
+            toml::from_str(std::str::from_utf8(files[Path::new(MANIFEST_FILE)]).unwrap()).unwrap();
+        assert_eq!(manifest.files.copy, ["layout.html"]);
+    }
+}
@@ -0,0 +1,385 @@
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Blob, Dynamic, Engine, Map, Scope, AST};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::config::naming::ProjectIdentifiers;
+use crate::config::structure::{BackendStack, FrontendStack, ProjectConfig, ProjectType};
+use crate::error::DevgeiniError;
+use crate::templates::engine::override_dir;
+use crate::utils::writer::{is_inside_project, ProjectWriter};
+
+/// Hook scripts live in this directory of a project template, or of the override directory
+/// for the built-in stacks.
+pub const HOOKS_DIR: &str = "hooks";
+/// Runs before anything is rendered and may change `variables`.
+pub const PRE_HOOK: &str = "pre_gen.rhai";
+/// Runs after everything is rendered, before it is written, and may change `files`.
+pub const POST_HOOK: &str = "post_gen.rhai";
+
+// Enough for any reasonable script, but stops an endless loop from hanging devgeini
+const MAX_OPERATIONS: u64 = 10_000_000;
+
+/// What hooks see as `config`. Project templates only know the project name.
+#[derive(Debug, Serialize)]
+pub struct HookConfig<'a> {
+    pub project_name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_type: Option<ProjectType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frontend_stack: Option<&'a FrontendStack>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend_stack: Option<&'a BackendStack>,
+    pub identifiers: ProjectIdentifiers,
+}
+
+impl<'a> HookConfig<'a> {
+    pub fn from_project(config: &'a ProjectConfig) -> Self {
+        HookConfig {
+            project_name: &config.name,
+            project_type: Some(config.project_type),
+            frontend_stack: config.frontend_stack.as_ref(),
+            backend_stack: config.backend_stack.as_ref(),
+            identifiers: config.identifiers.clone(),
+        }
+    }
+
+    pub fn from_name(name: &'a str) -> Self {
+        HookConfig {
+            project_name: name,
+            project_type: None,
+            frontend_stack: None,
+            backend_stack: None,
+            identifiers: ProjectIdentifiers::from_name(name),
+        }
+    }
+}
+
+struct Script {
+    path: PathBuf,
+    ast: AST,
+}
+
+/// The pre- and post-generation scripts of a template, written in [Rhai](https://rhai.rs).
+///
+/// Scripts run sandboxed: they cannot touch the file system, import modules or run commands,
+/// and only see the variables devgeini gives them. What they `print` is collected rather than
+/// written to the terminal and returned by `run`.
+pub struct Hooks {
+    engine: Engine,
+    output: Arc<Mutex<Vec<String>>>,
+    pre: Option<Script>,
+    post: Option<Script>,
+}
+
+impl Hooks {
+    /// Compiles the scripts in `dir/hooks`; either of them may be missing.
+    pub fn load(dir: &Path) -> Result<Self, DevgeiniError> {
+        let output = Arc::default();
+        let engine = sandboxed_engine(&output);
+        let hooks_dir = dir.join(HOOKS_DIR);
+        let pre = compile(&engine, &hooks_dir.join(PRE_HOOK))?;
+        let post = compile(&engine, &hooks_dir.join(POST_HOOK))?;
+        Ok(Hooks { engine, output, pre, post })
+    }
+
+    /// The hooks for the built-in stacks, from `~/.config/devgeini/templates/hooks`.
+    pub fn for_builtin() -> Result<Self, DevgeiniError> {
+        match override_dir() {
+            Some(dir) => Hooks::load(&dir),
+            None => {
+                let output = Arc::default();
+                Ok(Hooks {
+                    engine: sandboxed_engine(&output),
+                    output,
+                    pre: None,
+                    post: None,
+                })
+            }
+        }
+    }
+
+    /// Runs `generate` with the variables left by the pre hook, then lets the post hook
+    /// change its files before they reach `writer`.
+    ///
+    /// `variables` starts out as the template answers, or empty for the built-in stacks.
+    /// Returns the lines the scripts printed.
+    pub fn run<F>(
+        &self,
+        config: &HookConfig,
+        answers: &BTreeMap<String, toml::Value>,
+        mut variables: BTreeMap<String, toml::Value>,
+        writer: &mut dyn ProjectWriter,
+        generate: F,
+    ) -> Result<Vec<String>, DevgeiniError>
+    where
+        F: FnOnce(&BTreeMap<String, toml::Value>, &mut dyn ProjectWriter) -> Result<(), DevgeiniError>,
+    {
+        let config = to_dynamic(config, "config")?;
+        let answers = to_dynamic(answers, "answers")?;
+
+        if let Some(script) = &self.pre {
+            let mut scope = Scope::new();
+            scope.push_constant_dynamic("config", config.clone());
+            scope.push_constant_dynamic("answers", answers.clone());
+            scope.push_dynamic("variables", to_dynamic(&variables, "variables")?);
+            self.eval(script, &mut scope)?;
+
+            let value = scope.get_value::<Dynamic>("variables").unwrap_or_default();
+            variables = rhai::serde::from_dynamic(&value)
+                .map_err(|e| hook_error(script, format!("'variables' can only hold strings, numbers, booleans, arrays and maps: {}", e)))?;
+        }
+
+        let Some(script) = &self.post else {
+            generate(&variables, writer)?;
+            return Ok(self.take_output());
+        };
+
+        // Hold everything back so the script sees the complete file list
+        let mut buffer = BufferWriter::default();
+        generate(&variables, &mut buffer)?;
+
+        let mut scope = Scope::new();
+        scope.push_constant_dynamic("config", config);
+        scope.push_constant_dynamic("answers", answers);
+        scope.push_constant_dynamic("variables", to_dynamic(&variables, "variables")?);
+        scope.push("files", buffer.files_map());
+        self.eval(script, &mut scope)?;
+
+        let files = scope
+            .get_value::<Map>("files")
+            .ok_or_else(|| hook_error(script, "'files' has to stay a map of paths to contents".to_string()))?;
+        buffer.apply(script, files, writer)?;
+        Ok(self.take_output())
+    }
+
+    fn take_output(&self) -> Vec<String> {
+        std::mem::take(&mut *self.output.lock().unwrap())
+    }
+
+    fn eval(&self, script: &Script, scope: &mut Scope) -> Result<(), DevgeiniError> {
+        self.engine
+            .run_ast_with_scope(scope, &script.ast)
+            .map_err(|e| hook_error(script, e.to_string()))
+    }
+}
+
+fn sandboxed_engine(output: &Arc<Mutex<Vec<String>>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.set_max_modules(0);
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(64);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(10 * 1024 * 1024);
+    engine.set_max_array_size(100_000);
+    engine.set_max_map_size(100_000);
+    let print_output = Arc::clone(output);
+    engine.on_print(move |text| print_output.lock().unwrap().push(text.to_string()));
+    let debug_output = Arc::clone(output);
+    engine.on_debug(move |text, _, _| debug_output.lock().unwrap().push(text.to_string()));
+    engine
+}
+
+fn compile(engine: &Engine, path: &Path) -> Result<Option<Script>, DevgeiniError> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let source = fs::read_to_string(path).map_err(|e| DevgeiniError::io(path, e))?;
+    let ast = engine.compile(source).map_err(|e| DevgeiniError::Hook {
+        script: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    Ok(Some(Script {
+        path: path.to_path_buf(),
+        ast,
+    }))
+}
+
+fn to_dynamic<T: Serialize + ?Sized>(value: &T, name: &str) -> Result<Dynamic, DevgeiniError> {
+    rhai::serde::to_dynamic(value).map_err(|e| DevgeiniError::Template(format!("could not pass '{}' to the hooks: {}", name, e)))
+}
+
+fn hook_error(script: &Script, message: String) -> DevgeiniError {
+    DevgeiniError::Hook {
+        script: script.path.clone(),
+        message,
+    }
+}
+
+// Collects the generated project so the post hook can edit it before anything is written
+#[derive(Default)]
+struct BufferWriter {
+    dirs: Vec<PathBuf>,
+    files: Vec<(PathBuf, Vec<u8>)>,
+}
+
+impl ProjectWriter for BufferWriter {
+    fn add_dir(&mut self, path: &Path) -> io::Result<()> {
+        self.dirs.push(path.to_path_buf());
+        Ok(())
+    }
+
+    fn add_file(&mut self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        match self.files.iter_mut().find(|(existing, _)| existing == path) {
+            Some((_, existing)) => *existing = contents.to_vec(),
+            None => self.files.push((path.to_path_buf(), contents.to_vec())),
+        }
+        Ok(())
+    }
+}
+
+impl BufferWriter {
+    // Text files become strings, anything else a blob of bytes
+    fn files_map(&self) -> Map {
+        self.files
+            .iter()
+            .map(|(path, contents)| {
+                let contents = match std::str::from_utf8(contents) {
+                    Ok(text) => Dynamic::from(text.to_string()),
+                    Err(_) => Dynamic::from_blob(contents.clone()),
+                };
+                (file_key(path).into(), contents)
+            })
+            .collect()
+    }
+
+    // Writes what is left in `files`: the original files in their order, then the new ones
+    fn apply(self, script: &Script, mut files: Map, writer: &mut dyn ProjectWriter) -> Result<(), DevgeiniError> {
+        for dir in &self.dirs {
+            writer.create_dir_all(dir)?;
+        }
+        for (path, _) in &self.files {
+            if let Some(contents) = files.remove(file_key(path).as_str()) {
+                writer.write(path, file_contents(script, path, contents)?)?;
+            }
+        }
+        for (key, contents) in files {
+            let path = PathBuf::from(key.as_str());
+            if !is_inside_project(&path) || key.is_empty() {
+                return Err(hook_error(script, format!("'{}' is not a path inside the project", key)));
+            }
+            writer.write(&path, file_contents(script, &path, contents)?)?;
+        }
+        Ok(())
+    }
+}
+
+fn file_contents(script: &Script, path: &Path, contents: Dynamic) -> Result<Vec<u8>, DevgeiniError> {
+    if contents.is_string() {
+        return Ok(contents.into_string().unwrap_or_default().into_bytes());
+    }
+    contents.try_cast::<Blob>().ok_or_else(|| {
+        hook_error(
+            script,
+            format!("the contents of '{}' have to be a string or a blob", file_key(path)),
+        )
+    })
+}
+
+// Paths always use forward slashes in scripts, whatever the host OS
+fn file_key(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::templates::external::{ExternalTemplate, MANIFEST_FILE};
+    use crate::utils::writer::MemoryWriter;
+
+    fn write_hooks(dir: &Path, pre: Option<&str>, post: Option<&str>) {
+        let hooks_dir = dir.join(HOOKS_DIR);
+        fs::create_dir_all(&hooks_dir).unwrap();
+        for (file, script) in [(PRE_HOOK, pre), (POST_HOOK, post)] {
+            if let Some(script) = script {
+                fs::write(hooks_dir.join(file), script).unwrap();
+            }
+        }
+    }
+
+    fn run_hooks(
+        pre: Option<&str>,
+        post: Option<&str>,
+        answers: BTreeMap<String, toml::Value>,
+        writer: &mut MemoryWriter,
+    ) -> Result<Vec<String>, DevgeiniError> {
+        let dir = tempfile::tempdir().unwrap();
+        write_hooks(dir.path(), pre, post);
+        let hooks = Hooks::load(dir.path()).unwrap();
+
+        hooks.run(&HookConfig::from_name("my-shop"), &answers, answers.clone(), writer, |_, writer| {
+            writer.write("README.md", "# my-shop\n")?;
+            writer.write("Dockerfile", "FROM node:20\n")
+        })
+    }
+
+    #[test]
+    fn pre_hook_variables_reach_the_template() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(MANIFEST_FILE),
+            "name = \"service\"\n\n[[prompt]]\nkey = \"database\"\nchoices = [\"postgres\", \"mysql\"]\n",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.path().join("template")).unwrap();
+        fs::write(dir.path().join("template").join("db.env"), "DB_PORT={{ port }}\n").unwrap();
+        write_hooks(
+            dir.path(),
+            Some(r#"variables.port = if answers.database == "postgres" { 5432 } else { 3306 }; print("port set");"#),
+            None,
+        );
+
+        let template = ExternalTemplate::fetch(dir.path().to_str().unwrap()).unwrap();
+        let answers = template.ask(&BTreeMap::new(), "my-shop", None).unwrap();
+        let mut writer = MemoryWriter::new();
+        let output = template.render("my-shop", &answers, &mut writer).unwrap();
+
+        let files: BTreeMap<_, _> = writer.files().collect();
+        assert_eq!(files[Path::new("db.env")], b"DB_PORT=5432\n");
+        assert_eq!(output, ["port set"]);
+    }
+
+    #[test]
+    fn post_hook_adds_and_removes_files() {
+        let post = r#"
+            if !answers.docker { files.remove("Dockerfile"); }
+            files["NOTICE"] = `Copyright ${config.identifiers.pascal}`;
+        "#;
+        let answers = BTreeMap::from([("docker".to_string(), toml::Value::Boolean(false))]);
+        let mut writer = MemoryWriter::new();
+        run_hooks(None, Some(post), answers, &mut writer).unwrap();
+
+        assert_eq!(writer.file_names(), ["NOTICE", "README.md"]);
+        let files: BTreeMap<_, _> = writer.files().collect();
+        assert_eq!(files[Path::new("NOTICE")], b"Copyright MyShop");
+    }
+
+    #[test]
+    fn post_hook_cannot_add_files_outside_the_project() {
+        let mut writer = MemoryWriter::new();
+        let result = run_hooks(None, Some(r#"files["../x"] = "escaped";"#), BTreeMap::new(), &mut writer);
+
+        assert!(matches!(result, Err(DevgeiniError::Hook { .. })), "{:?}", result);
+        assert!(!writer.file_names().iter().any(|name| name.contains("..")));
+    }
+
+    #[test]
+    fn endless_loop_is_stopped() {
+        let mut writer = MemoryWriter::new();
+        let result = run_hooks(Some("loop { }"), None, BTreeMap::new(), &mut writer);
+
+        match result {
+            Err(DevgeiniError::Hook { message, .. }) => assert!(message.contains("operations"), "{}", message),
+            other => panic!("expected a hook error, got {:?}", other),
+        }
+        assert_eq!(writer.files().count(), 0);
+    }
+}
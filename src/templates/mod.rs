@@ -1,2 +1,6 @@
-pub mod frontend;
-pub mod backend;
\ No newline at end of file
+pub mod engine;
+pub mod external;
+pub mod cookiecutter;
+pub mod extract;
+pub mod hooks;
+pub mod registry;
@@ -0,0 +1,182 @@
+use serde::Serialize;
+use std::fs;
+
+use crate::config::project_types::PROJECT_TYPES;
+use crate::config::structure::ProjectType;
+use crate::stacks::registry::StackRegistry;
+use crate::stacks::StackKind;
+use crate::templates::external::{installed_dir, is_template_dir, load_template};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EntryKind {
+    ProjectType,
+    Frontend,
+    Backend,
+    Template,
+}
+
+impl EntryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryKind::ProjectType => "project-type",
+            EntryKind::Frontend => "frontend",
+            EntryKind::Backend => "backend",
+            EntryKind::Template => "template",
+        }
+    }
+
+    pub fn heading(&self) -> &'static str {
+        match self {
+            EntryKind::ProjectType => "📋 Project types",
+            EntryKind::Frontend => "🎨 Frontend stacks",
+            EntryKind::Backend => "⚙️  Backend stacks",
+            EntryKind::Template => "🧩 Installed templates",
+        }
+    }
+}
+
+/// One thing devgeini can generate, as shown by `devgeini list` and `devgeini search`.
+#[derive(Debug, Serialize)]
+pub struct TemplateEntry {
+    pub id: String,
+    pub kind: EntryKind,
+    pub name: String,
+    pub description: String,
+    pub language: String,
+    pub project_types: Vec<ProjectType>,
+    /// The command that generates it
+    pub usage: String,
+}
+
+impl TemplateEntry {
+    /// Case-insensitive match against everything `list` shows.
+    pub fn matches(&self, term: &str) -> bool {
+        let term = term.to_lowercase();
+
+        [self.id.as_str(), &self.name, &self.description, &self.language, self.kind.as_str()]
+            .into_iter()
+            .any(|field| field.to_lowercase().contains(&term))
+            || self.project_types.iter().any(|project_type| project_type.to_string().contains(&term))
+    }
+}
+
+/// What `template_index` found, with the reasons for anything it had to leave out.
+#[derive(Debug, Default)]
+pub struct TemplateIndex {
+    pub entries: Vec<TemplateEntry>,
+    pub warnings: Vec<String>,
+}
+
+/// Every project type and the stacks of `stacks`, followed by the installed templates.
+///
+/// Installed templates that cannot be read are left out with a warning, next to the
+/// registry's own warnings about skipped plugins.
+pub fn template_index(stacks: &StackRegistry) -> TemplateIndex {
+    let mut index = TemplateIndex::default();
+    let entries = &mut index.entries;
+
+    for info in PROJECT_TYPES {
+        entries.push(TemplateEntry {
+            id: info.project_type.to_string(),
+            kind: EntryKind::ProjectType,
+            name: info.display_name.to_string(),
+            description: info.description.to_string(),
+            language: info.language.to_string(),
+            project_types: vec![info.project_type],
+            usage: format!("devgeini init --type {}", info.project_type),
+        });
+    }
+    for (stack_kind, kind) in [(StackKind::Frontend, EntryKind::Frontend), (StackKind::Backend, EntryKind::Backend)] {
+        for stack in stacks.of_kind(stack_kind) {
+            entries.push(TemplateEntry {
+                id: stack.id().to_string(),
+                kind,
+                name: stack.display_name().to_string(),
+                description: stack.description().to_string(),
+                language: stack.language().to_string(),
+                project_types: stack.project_types().to_vec(),
+                usage: format!("devgeini init --type {0} --{0} {1}", stack_kind.dir(), stack.id()),
+            });
+        }
+    }
+
+    index.warnings.extend(stacks.warnings().iter().cloned());
+    add_installed_templates(&mut index);
+    index
+}
+
+fn add_installed_templates(index: &mut TemplateIndex) {
+    let Some(Ok(dir_entries)) = installed_dir().map(fs::read_dir) else {
+        return;
+    };
+
+    let mut dirs: Vec<_> = dir_entries.flatten().map(|entry| entry.path()).filter(|path| is_template_dir(path)).collect();
+    dirs.sort();
+
+    for dir in dirs {
+        let id = dir.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        match load_template(&dir, &id) {
+            Ok((manifest, _)) => index.entries.push(TemplateEntry {
+                usage: format!("devgeini init --template {}", id),
+                id,
+                kind: EntryKind::Template,
+                name: manifest.name,
+                description: manifest.description,
+                language: manifest.language,
+                project_types: manifest.project_types,
+            }),
+            Err(e) => index.warnings.push(format!("skipping installed template '{}': {}", id, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::structure::BackendStack;
+
+    fn entry<'a>(index: &'a TemplateIndex, kind: EntryKind, id: &str) -> &'a TemplateEntry {
+        index.entries.iter().find(|entry| entry.kind == kind && entry.id == id).unwrap()
+    }
+
+    #[test]
+    fn entries_serialize_for_json_output() {
+        let index = template_index(&StackRegistry::with_builtins());
+
+        let go = serde_json::to_value(entry(&index, EntryKind::Backend, "go")).unwrap();
+        assert_eq!(
+            go,
+            serde_json::json!({
+                "id": "go",
+                "kind": "backend",
+                "name": "Go (net/http)",
+                "description": "HTTP service using only the standard library",
+                "language": "Go",
+                "project_types": ["fullstack", "backend"],
+                "usage": "devgeini init --type backend --backend go",
+            })
+        );
+        let cli = serde_json::to_value(entry(&index, EntryKind::ProjectType, "cli")).unwrap();
+        assert_eq!(cli["kind"], "project-type");
+        assert_eq!(cli["usage"], "devgeini init --type cli");
+    }
+
+    #[test]
+    fn search_matches_any_shown_field() {
+        let index = template_index(&StackRegistry::with_builtins());
+        let found = |term: &str| -> Vec<&str> {
+            index
+                .entries
+                .iter()
+                .filter(|entry| entry.kind == EntryKind::Backend && entry.matches(term))
+                .map(|entry| entry.id.as_str())
+                .collect()
+        };
+
+        assert_eq!(found("GRADLE"), ["java-gradle"]);
+        assert!(found("php").contains(&"php"));
+        assert!(found("nothing-like-this").is_empty());
+        assert_eq!(found("backend").len(), BackendStack::BUILTIN.len());
+    }
+}
@@ -1,171 +1,154 @@
-use clap::{Arg, Command};
-use std::path::Path;
-use dialoguer::{theme::ColorfulTheme, Select, Input, Confirm};
-use serde_json::json;
-use std::fs;
+use std::collections::BTreeMap;
 
-use crate::config::structure::{ProjectConfig, FrontendStack, BackendStack, ProjectType};
+use crate::config::structure::{ProjectConfig, ProjectType};
+use crate::error::DevgeiniError;
+use crate::templates::hooks::{HookConfig, Hooks};
+use crate::utils::writer::ProjectWriter;
 use crate::utils::projecttype::{
-    create_fullstack_project, create_frontend_project, create_backend_project,
+    create_fullstack_project, create_single_stack_project,
     create_cli_project, create_extension_project, create_gitignore, create_readme, create_env_file,
+    create_project_config_file,
 };
 
-
-pub fn get_project_name() -> String {
-    Input::with_theme(&ColorfulTheme::default())
-        .with_prompt("Enter project name")
-        .interact_text()
-        .unwrap()
+/// Runs every generator for `config` against `writer`, with paths relative to the project root,
+/// between the user's pre- and post-generation hooks. Returns the lines the hooks printed.
+pub fn write_project(config: &ProjectConfig, writer: &mut dyn ProjectWriter) -> Result<Vec<String>, DevgeiniError> {
+    let hooks = Hooks::for_builtin()?;
+    hooks.run(&HookConfig::from_project(config), &BTreeMap::new(), BTreeMap::new(), writer, |variables, writer| {
+        let mut config = config.clone();
+        config.variables = variables.clone();
+        write_project_files(&config, writer)
+    })
 }
 
-pub fn get_project_config_interactive(name: String) -> ProjectConfig {
-    let project_type = select_project_type();
-    
-    let mut config = ProjectConfig {
-        name,
-        project_type: project_type.clone(),
-        frontend_stack: None,
-        backend_stack: None,
-    };
-
-    match project_type {
-        ProjectType::FullStackWeb => {
-            config.frontend_stack = Some(select_frontend_stack());
-            config.backend_stack = Some(select_backend_stack());
-        }
-        ProjectType::Frontend => {
-            config.frontend_stack = Some(select_frontend_stack());
-        }
-        ProjectType::Backend => {
-            config.backend_stack = Some(select_backend_stack());
-        }
-        _ => {}
+fn write_project_files(config: &ProjectConfig, writer: &mut dyn ProjectWriter) -> Result<(), DevgeiniError> {
+    // Create project structure
+    match config.project_type {
+        ProjectType::FullStackWeb => create_fullstack_project(config, writer)?,
+        ProjectType::Frontend | ProjectType::Backend => create_single_stack_project(config, writer)?,
+        ProjectType::CliTool => create_cli_project(config, writer)?,
+        ProjectType::WebExtension => create_extension_project(config, writer)?,
     }
 
-    config
+    // Create common files
+    create_gitignore(config, writer)?;
+    create_readme(config, writer)?;
+    create_env_file(config, writer)?;
+    create_project_config_file(config, writer)?;
+
+    Ok(())
 }
 
-pub fn select_project_type() -> ProjectType {
-    let options = vec![
-        "Full Stack Web Application",
-        "Frontend Only",
-        "Backend Only", 
-        "CLI Tool",
-        "Web Extension",
-    ];
-
-    let selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Select project type")
-        .items(&options)
-        .default(0)
-        .interact()
-        .unwrap();
-
-    match selection {
-        0 => ProjectType::FullStackWeb,
-        1 => ProjectType::Frontend,
-        2 => ProjectType::Backend,
-        3 => ProjectType::CliTool,
-        4 => ProjectType::WebExtension,
-        _ => ProjectType::FullStackWeb,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::structure::Database;
+    use crate::utils::writer::MemoryWriter;
+    use std::path::Path;
+
+    fn generated_files(project_type: ProjectType, frontend: Option<&str>, backend: Option<&str>) -> Vec<String> {
+        let mut config = ProjectConfig::new("shop".to_string(), project_type);
+        config.frontend_stack = frontend.map(|id| id.parse().unwrap());
+        config.backend_stack = backend.map(|id| id.parse().unwrap());
+        config.validate().unwrap();
+
+        let mut writer = MemoryWriter::new();
+        write_project_files(&config, &mut writer).unwrap();
+        writer.file_names()
     }
-}
 
-pub fn select_frontend_stack() -> FrontendStack {
-    let options = vec![
-        "React (TypeScript)",
-        "Vue.js (TypeScript)", 
-        "Svelte (TypeScript)",
-        "Next.js (TypeScript)",
-        "Vanilla JavaScript",
-    ];
-
-    let selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Select frontend technology")
-        .items(&options)
-        .default(0)
-        .interact()
-        .unwrap();
-
-    match selection {
-        0 => FrontendStack::ReactTs,
-        1 => FrontendStack::React,
-        2 => FrontendStack::VueTs,
-        3 => FrontendStack::Vue,
-        4 => FrontendStack::Angular,
-        5 => FrontendStack::SvelteTs,
-        6 => FrontendStack::Svelte,
-        7 => FrontendStack::NextJsTs,
-        8 => FrontendStack::NextJs,
-        9 => FrontendStack::VanillaTs,
-        10 => FrontendStack::Vanilla,
-        _ => FrontendStack::ReactTs,
+    #[test]
+    fn fullstack_project_files() {
+        assert_eq!(
+            generated_files(ProjectType::FullStackWeb, Some("react-ts"), Some("go")),
+            [
+                ".env", ".env.example", ".gitignore", "README.md", "backend/Dockerfile",
+                "backend/cmd/server/main.go", "backend/go.mod", "backend/internal/config/config.go",
+                "backend/internal/handlers/health.go", "backend/internal/handlers/health_test.go",
+                "backend/internal/server/router.go", "docker-compose.yml", "frontend/Dockerfile",
+                "frontend/index.html", "frontend/nginx.conf", "frontend/package.json", "frontend/src/App.css",
+                "frontend/src/App.tsx", "frontend/src/index.css", "frontend/src/main.tsx",
+                "frontend/tsconfig.json", "frontend/tsconfig.node.json", "frontend/vite.config.ts",
+                "package.json", "project_config.toml",
+            ]
+        );
     }
-}
 
-pub fn select_backend_stack() -> BackendStack {
-    let options = vec![
-        "Node.js (TypeScript)",
-        "Node.js (JavaScript)",
-        "Python (FastAPI)",
-        "Rust (Actix)",
-        "Go"
-    ];
-
-    let selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Select backend technology")
-        .items(&options)
-        .default(0)
-        .interact()
-        .unwrap();
-
-    match selection {
-        0 => BackendStack::NodeJsTs,
-        1 => BackendStack::NodeJs,
-        2 => BackendStack::Python,
-        3 => BackendStack::Rust,
-        4 => BackendStack::Go,
-        5 => BackendStack::Java,
-        6 => BackendStack::Php,
-        _ => BackendStack::NodeJsTs,
+    #[test]
+    fn frontend_project_files() {
+        assert_eq!(
+            generated_files(ProjectType::Frontend, Some("vue"), None),
+            [
+                ".env", ".env.example", ".gitignore", "Dockerfile", "README.md", "index.html", "nginx.conf",
+                "package.json", "project_config.toml", "src/App.vue", "src/main.js", "vite.config.js",
+            ]
+        );
     }
-}
 
-pub fn create_project(config: &ProjectConfig) -> Result<(), Box<dyn std::error::Error>> {
-    let project_path = Path::new(&config.name);
-    
-    if project_path.exists() {
-        let overwrite = Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Project directory already exists. Overwrite?")
-            .interact()?;
-        
-        if !overwrite {
-            return Ok(());
-        }
-        
-        fs::remove_dir_all(project_path)?;
+    #[test]
+    fn backend_project_files() {
+        assert_eq!(
+            generated_files(ProjectType::Backend, None, Some("python")),
+            [
+                ".env", ".env.example", ".gitignore", "Dockerfile", "README.md", "main.py",
+                "project_config.toml", "requirements.txt",
+            ]
+        );
     }
 
-    fs::create_dir_all(project_path)?;
+    #[test]
+    fn cli_project_files() {
+        assert_eq!(
+            generated_files(ProjectType::CliTool, None, None),
+            [".env", ".env.example", ".gitignore", "Cargo.toml", "README.md", "project_config.toml", "src/main.rs"]
+        );
+    }
 
-    // Create project structure
-    match config.project_type {
-        ProjectType::FullStackWeb => create_fullstack_project(config)?,
-        ProjectType::Frontend => create_frontend_project(config)?,
-        ProjectType::Backend => create_backend_project(config)?,
-        ProjectType::CliTool => create_cli_project(config)?,
-        ProjectType::WebExtension => create_extension_project(config)?,
+    #[test]
+    fn extension_project_files() {
+        assert_eq!(
+            generated_files(ProjectType::WebExtension, None, None),
+            [
+                ".env", ".env.example", ".gitignore", "README.md", "background.js", "content.js",
+                "manifest.json", "package.json", "popup.html", "popup.js", "project_config.toml",
+            ]
+        );
     }
 
-    // Create common files
-    create_gitignore(config)?;
-    create_readme(config)?;
-    create_env_file(config)?;
+    fn file_contents(config: &ProjectConfig, path: &str) -> String {
+        let mut writer = MemoryWriter::new();
+        write_project_files(config, &mut writer).unwrap();
+        let (_, contents) = writer.files().find(|(file, _)| *file == Path::new(path)).unwrap();
+        String::from_utf8(contents.to_vec()).unwrap()
+    }
 
-    // 🎯 NEW: Auto-install dependencies
-    println!("\n🔧 Setting up project dependencies...");
-    
-    
+    #[test]
+    fn database_choice_reaches_env_and_compose() {
+        let mut config = ProjectConfig::new("shop".to_string(), ProjectType::FullStackWeb);
+        config.frontend_stack = Some("react".parse().unwrap());
+        config.backend_stack = Some("node".parse().unwrap());
+
+        let compose = file_contents(&config, "docker-compose.yml");
+        assert!(!compose.contains("database"), "{}", compose);
+        assert!(!compose.contains("volumes:"), "{}", compose);
+        assert!(!file_contents(&config, ".env.example").contains("DATABASE_URL"));
+
+        config.database = Some(Database::Mongodb);
+        let compose = file_contents(&config, "docker-compose.yml");
+        assert!(compose.contains("image: mongo:7"), "{}", compose);
+        assert!(compose.contains("depends_on:\n      - database"), "{}", compose);
+        assert!(compose.contains("  mongo_data:"), "{}", compose);
+        assert!(file_contents(&config, ".env.example").contains("DATABASE_URL=mongodb://localhost:27017/shop"));
+    }
 
-    Ok(())
+    #[test]
+    fn frontend_dockerfile_builds_with_dev_dependencies() {
+        let mut config = ProjectConfig::new("shop".to_string(), ProjectType::Frontend);
+        config.frontend_stack = Some("angular".parse().unwrap());
+
+        let dockerfile = file_contents(&config, "Dockerfile");
+        assert!(dockerfile.contains("RUN npm ci\n"), "{}", dockerfile);
+        assert!(!dockerfile.contains("--only=production"), "{}", dockerfile);
+        assert!(dockerfile.contains("COPY --from=builder /app/dist/shop/browser "), "{}", dockerfile);
+    }
 }
@@ -1,3 +1,3 @@
 pub mod createproject;
 pub mod projecttype;
-pub mod createfiles;
\ No newline at end of file
+pub mod writer;
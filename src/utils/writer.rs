@@ -0,0 +1,370 @@
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+
+use crate::error::DevgeiniError;
+
+/// Destination for everything a generator produces.
+///
+/// Paths are always relative to the project root; the writer decides where they end up
+/// (a directory on disk, memory for `--dry-run`, or a zip/tar archive).
+pub trait ProjectWriter {
+    fn add_dir(&mut self, path: &Path) -> io::Result<()>;
+    fn add_file(&mut self, path: &Path, contents: &[u8]) -> io::Result<()>;
+
+    /// Flushes any buffered output. Must be called once generation is complete.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Whether `path` stays below the directory it is joined to: no `..`, root or drive prefix.
+pub fn is_inside_project(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+// Rendered file names come from templates, answers and hooks, so they are checked before anything is written
+fn check_inside_project(path: &Path) -> io::Result<()> {
+    if is_inside_project(path) {
+        return Ok(());
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "the path points outside the project directory",
+    ))
+}
+
+// Convenience wrappers so generators can pass string literals and String contents directly,
+// and failures name the file that could not be written
+impl dyn ProjectWriter + '_ {
+    pub fn create_dir_all<P: AsRef<Path>>(&mut self, path: P) -> Result<(), DevgeiniError> {
+        let path = path.as_ref();
+        check_inside_project(path)
+            .and_then(|_| self.add_dir(path))
+            .map_err(|e| DevgeiniError::io(path, e))
+    }
+
+    pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(&mut self, path: P, contents: C) -> Result<(), DevgeiniError> {
+        let path = path.as_ref();
+        check_inside_project(path)
+            .and_then(|_| self.add_file(path, contents.as_ref()))
+            .map_err(|e| DevgeiniError::io(path, e))
+    }
+
+    /// A writer that places everything below `dir`, e.g. the `frontend/` half of a full-stack project.
+    pub fn scoped<P: AsRef<Path>>(&mut self, dir: P) -> ScopedWriter<'_> {
+        ScopedWriter {
+            inner: self,
+            prefix: dir.as_ref().to_path_buf(),
+        }
+    }
+}
+
+pub struct ScopedWriter<'a> {
+    inner: &'a mut dyn ProjectWriter,
+    prefix: PathBuf,
+}
+
+impl ProjectWriter for ScopedWriter<'_> {
+    fn add_dir(&mut self, path: &Path) -> io::Result<()> {
+        self.inner.add_dir(&self.prefix.join(path))
+    }
+
+    fn add_file(&mut self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        self.inner.add_file(&self.prefix.join(path), contents)
+    }
+}
+
+/// Writes straight to a directory on disk.
+pub struct DiskWriter {
+    root: PathBuf,
+}
+
+impl DiskWriter {
+    pub fn new(root: &Path) -> Self {
+        DiskWriter { root: root.to_path_buf() }
+    }
+}
+
+impl ProjectWriter for DiskWriter {
+    fn add_dir(&mut self, path: &Path) -> io::Result<()> {
+        check_inside_project(path)?;
+        fs::create_dir_all(self.root.join(path))
+    }
+
+    fn add_file(&mut self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        check_inside_project(path)?;
+        let target = self.root.join(path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, contents)
+    }
+}
+
+enum Entry {
+    Dir,
+    File(Vec<u8>),
+}
+
+/// Keeps the generated project in memory, keyed by relative path.
+#[derive(Default)]
+pub struct MemoryWriter {
+    entries: BTreeMap<PathBuf, Entry>,
+}
+
+impl ProjectWriter for MemoryWriter {
+    fn add_dir(&mut self, path: &Path) -> io::Result<()> {
+        check_inside_project(path)?;
+        for ancestor in path.ancestors() {
+            if ancestor.as_os_str().is_empty() {
+                break;
+            }
+            self.entries.entry(ancestor.to_path_buf()).or_insert(Entry::Dir);
+        }
+        Ok(())
+    }
+
+    fn add_file(&mut self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        check_inside_project(path)?;
+        if let Some(parent) = path.parent() {
+            self.add_dir(parent)?;
+        }
+        self.entries.insert(path.to_path_buf(), Entry::File(contents.to_vec()));
+        Ok(())
+    }
+}
+
+impl MemoryWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = (&Path, &[u8])> {
+        self.entries.iter().filter_map(|(path, entry)| match entry {
+            Entry::File(contents) => Some((path.as_path(), contents.as_slice())),
+            Entry::Dir => None,
+        })
+    }
+
+    /// Every recorded directory, including the parents of files.
+    pub fn dirs(&self) -> impl Iterator<Item = &Path> {
+        self.entries.iter().filter_map(|(path, entry)| match entry {
+            Entry::Dir => Some(path.as_path()),
+            Entry::File(_) => None,
+        })
+    }
+
+    /// The recorded file paths with `/` separators, sorted, for comparing in tests.
+    #[cfg(test)]
+    pub(crate) fn file_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .files()
+            .map(|(path, _)| path.to_string_lossy().replace('\\', "/"))
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+/// Packs the project into a `.zip` or `.tar.gz` archive below a top-level `<root_name>/` directory.
+///
+/// Entries are kept in memory until `finish`, so a file written twice ends up in the archive once
+/// with its last contents, like on disk. The archive is written next to `path` and only renamed
+/// into place once it is complete, so a failed generation never replaces an existing file.
+pub struct ArchiveWriter {
+    path: Option<PathBuf>,
+    format: ArchiveFormat,
+    root: PathBuf,
+    entries: BTreeMap<PathBuf, Entry>,
+}
+
+#[derive(Clone, Copy)]
+enum ArchiveFormat {
+    Zip,
+    TarGz,
+}
+
+impl ArchiveWriter {
+    /// Picks the archive format from the file extension of `path`.
+    pub fn create(path: &Path, root_name: &str) -> io::Result<Self> {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+        let format = if file_name.ends_with(".zip") {
+            ArchiveFormat::Zip
+        } else if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+            ArchiveFormat::TarGz
+        } else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unsupported archive type '{}' (use .zip, .tar.gz or .tgz)", path.display()),
+            ));
+        };
+
+        Ok(ArchiveWriter {
+            path: Some(path.to_path_buf()),
+            format,
+            root: PathBuf::from(root_name),
+            entries: BTreeMap::new(),
+        })
+    }
+
+    fn write_to(&self, file: File) -> io::Result<()> {
+        match self.format {
+            ArchiveFormat::Zip => {
+                let mut zip = zip::ZipWriter::new(file);
+                for (path, entry) in &self.entries {
+                    let name = archive_name(&self.root, path);
+                    match entry {
+                        Entry::Dir => zip.add_directory(name, zip::write::FileOptions::default()),
+                        Entry::File(contents) => zip
+                            .start_file(name, zip::write::FileOptions::default())
+                            .and_then(|_| Ok(zip.write_all(contents)?)),
+                    }
+                    .map_err(zip_error)?;
+                }
+                zip.finish().map_err(zip_error)?.sync_all()
+            }
+            ArchiveFormat::TarGz => {
+                let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+                for (path, entry) in &self.entries {
+                    let name = archive_name(&self.root, path);
+                    let mut header = tar::Header::new_gnu();
+                    match entry {
+                        Entry::Dir => {
+                            header.set_entry_type(tar::EntryType::Directory);
+                            header.set_mode(0o755);
+                            header.set_size(0);
+                            header.set_cksum();
+                            tar.append_data(&mut header, format!("{}/", name), io::empty())?;
+                        }
+                        Entry::File(contents) => {
+                            header.set_mode(0o644);
+                            header.set_size(contents.len() as u64);
+                            header.set_cksum();
+                            tar.append_data(&mut header, name, contents.as_slice())?;
+                        }
+                    }
+                }
+                tar.into_inner()?.finish()?.sync_all()
+            }
+        }
+    }
+}
+
+// Archive entry names always use forward slashes, whatever the host OS
+fn archive_name(root: &Path, path: &Path) -> String {
+    root.join(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn zip_error(e: zip::result::ZipError) -> io::Error {
+    io::Error::other(e)
+}
+
+impl ProjectWriter for ArchiveWriter {
+    fn add_dir(&mut self, path: &Path) -> io::Result<()> {
+        check_inside_project(path)?;
+        self.entries.entry(path.to_path_buf()).or_insert(Entry::Dir);
+        Ok(())
+    }
+
+    fn add_file(&mut self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        check_inside_project(path)?;
+        self.entries.insert(path.to_path_buf(), Entry::File(contents.to_vec()));
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        let Some(path) = self.path.take() else {
+            return Ok(());
+        };
+
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let partial = path.with_file_name(format!(".{}.devgeini-partial-{}", file_name, std::process::id()));
+        let result = File::create(&partial)
+            .and_then(|file| self.write_to(file))
+            .and_then(|_| fs::rename(&partial, &path));
+        if result.is_err() {
+            let _ = fs::remove_file(&partial);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn archive_keeps_the_last_write_of_a_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shop.zip");
+
+        let mut archive = ArchiveWriter::create(&path, "shop").unwrap();
+        archive.add_dir(Path::new("src")).unwrap();
+        archive.add_file(Path::new(".env"), b"stale").unwrap();
+        archive.add_file(Path::new("src/main.rs"), b"fn main() {}").unwrap();
+        archive.add_file(Path::new(".env"), b"PORT=3000").unwrap();
+        assert!(!path.exists(), "nothing is written before finish");
+        archive.finish().unwrap();
+
+        let mut zip = zip::ZipArchive::new(File::open(&path).unwrap()).unwrap();
+        let mut names: Vec<&str> = zip.file_names().collect();
+        names.sort();
+        assert_eq!(names, ["shop/.env", "shop/src/", "shop/src/main.rs"]);
+
+        let mut env = String::new();
+        zip.by_name("shop/.env").unwrap().read_to_string(&mut env).unwrap();
+        assert_eq!(env, "PORT=3000");
+        assert_eq!(zip.by_name("shop/.env").unwrap().unix_mode(), Some(0o100644));
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn tar_entries_have_forward_slashes_and_modes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shop.tar.gz");
+
+        let mut archive = ArchiveWriter::create(&path, "shop").unwrap();
+        archive.add_file(Path::new("src/main.rs"), b"fn main() {}").unwrap();
+        archive.add_dir(Path::new("src")).unwrap();
+        archive.finish().unwrap();
+
+        let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(File::open(&path).unwrap()));
+        let entries: Vec<(String, u32)> = tar
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                let header = entry.header();
+                (header.path().unwrap().to_string_lossy().into_owned(), header.mode().unwrap())
+            })
+            .collect();
+        assert_eq!(entries, [("shop/src/".to_string(), 0o755), ("shop/src/main.rs".to_string(), 0o644)]);
+    }
+
+    #[test]
+    fn dry_run_and_disk_reject_the_same_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut disk = DiskWriter::new(dir.path());
+        let mut memory = MemoryWriter::new();
+
+        for path in ["../outside.txt", "/etc/passwd"] {
+            let on_disk = disk.add_file(Path::new(path), b"x").unwrap_err();
+            let in_memory = memory.add_file(Path::new(path), b"x").unwrap_err();
+            assert_eq!(on_disk.kind(), in_memory.kind());
+            assert_eq!(on_disk.to_string(), in_memory.to_string());
+        }
+        assert!(memory.add_dir(Path::new("../outside")).is_err());
+        assert_eq!(memory.files().count() + memory.dirs().count(), 0);
+        assert!(!dir.path().parent().unwrap().join("outside.txt").exists());
+    }
+}